@@ -0,0 +1,333 @@
+use chrono::{Local, NaiveDate};
+use prettytable::{format, Cell, Row, Table};
+use rusqlite::{params, Connection, Transaction};
+use std::error::Error;
+use timesheet_core::{sqlite::DAY_COLUMNS, week_date, Repository, WeekEntry};
+
+use crate::{
+    categories, format_hours,
+    locale::Catalog,
+    validate::{self, CellChange, Rejected, Rules, Severity, Violation},
+};
+
+// --- Change Sources ---
+// Stored as text in `changes.source`; later write paths (import, timer, API) add their own source.
+#[derive(Debug, Clone, Copy)]
+pub enum ChangeSource {
+    Prompt,
+    Api,
+    Undo,
+    Redo,
+    Sync, // changes of other devices, see sync.rs
+}
+
+impl ChangeSource {
+    fn code(self) -> &'static str {
+        match self {
+            ChangeSource::Prompt => "prompt",
+            ChangeSource::Api => "api",
+            ChangeSource::Undo => "undo",
+            ChangeSource::Redo => "redo",
+            ChangeSource::Sync => "sync",
+        }
+    }
+
+    // Undo, redo and sync are logged, but are not themselves pushed onto the undo stack
+    fn undoable(self) -> bool {
+        !matches!(self, ChangeSource::Undo | ChangeSource::Redo | ChangeSource::Sync)
+    }
+}
+
+// --- Change Set ---
+// Every write of hours goes through a ChangeSet: the hours (through the repository) and the
+// change log are written together, or not at all. All changes of one user action share a batch.
+pub struct ChangeSet<'c> {
+    tx: Transaction<'c>,
+    repo: &'c dyn Repository,
+    pending: Pending<'c>,
+    batch: i64,
+    source: ChangeSource,
+    changed_at: String,
+    cells: std::cell::RefCell<Vec<CellChange>>, // the hours changed so far
+    logged: std::cell::Cell<bool>, // anything written to the log, hours or billable flags
+}
+
+// Rolls back the repository's writes when the ChangeSet is dropped without a commit
+struct Pending<'c> {
+    repo: &'c dyn Repository,
+    open: bool,
+}
+
+impl Drop for Pending<'_> {
+    fn drop(&mut self) {
+        if self.open { self.repo.rollback(); }
+    }
+}
+
+// Result of a committed ChangeSet
+pub struct Committed {
+    pub batch: Option<i64>, // pushed onto the undo stack
+    pub warnings: Vec<Violation>,
+}
+
+impl<'c> ChangeSet<'c> {
+    pub fn begin(conn: &'c Connection, repo: &'c dyn Repository, source: ChangeSource) -> Result<ChangeSet<'c>, Box<dyn Error>> {
+        let tx = conn.unchecked_transaction()?;
+        let batch: i64 = tx.query_row("SELECT COALESCE(MAX(batch), 0) + 1 FROM changes", [], |row| row.get(0))?;
+        repo.begin()?;
+        Ok(ChangeSet {
+            tx,
+            repo,
+            pending: Pending { repo, open: true },
+            batch,
+            source,
+            changed_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            cells: std::cell::RefCell::new(Vec::new()),
+            logged: std::cell::Cell::new(false),
+        })
+    }
+
+    // `billable` is the row's own flag, kept with adds and removes so undo restores it
+    #[allow(clippy::too_many_arguments)]
+    fn log(&self, action: &str, week: &str, project: &str, category: &str, billable: Option<bool>, day: usize, old: f64, new: f64) -> Result<(), Box<dyn Error>> {
+        self.record(action, week, project, category, billable, day, old, new)?;
+        if let Some(date) = week_date(week, day) {
+            self.cells.borrow_mut().push(CellChange { week: week.to_string(), project: project.to_string(), date, old, new });
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn record(&self, action: &str, week: &str, project: &str, category: &str, billable: Option<bool>, day: usize, old: f64, new: f64) -> Result<(), Box<dyn Error>> {
+        self.tx.execute(
+            "INSERT INTO changes (batch, changed_at, source, action, week, project, category, billable, day, old_value, new_value)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![self.batch, self.changed_at, self.source.code(), action, week, project, category, billable, DAY_COLUMNS[day], old, new],
+        )?;
+        self.logged.set(true);
+        Ok(())
+    }
+
+    fn row_hours(&self, week: &str, project: &str, category: &str) -> Result<Option<[f64; 7]>, Box<dyn Error>> {
+        Ok(self.repo.entry(week, project, category)?.map(|e| e.hours))
+    }
+
+    // Adds a project row (with a category, or "") to a week, with its own billable flag or
+    // None to follow its project and category; fails when the row already exists
+    pub fn insert_row(&self, week: &str, project: &str, category: &str, hours: [f64; 7], billable: Option<bool>) -> Result<(), Box<dyn Error>> {
+        self.repo.insert_entry(&WeekEntry { week: week.to_string(), project: project.to_string(), category: category.to_string(), hours, billable })?;
+        self.log_row("add", week, project, category, billable, hours.map(|h| (0.0, h)))
+    }
+
+    // Logs the days of an added or removed row that have hours. A row without hours is logged
+    // as a marker on Monday (0 to 0), so it still reaches the history, undo and sync.
+    fn log_row(&self, action: &str, week: &str, project: &str, category: &str, billable: Option<bool>, days: [(f64, f64); 7]) -> Result<(), Box<dyn Error>> {
+        let empty = days.iter().all(|(old, new)| old == new);
+        for (day, &(old, new)) in days.iter().enumerate() {
+            if old != new || (empty && day == 0) {
+                self.log(action, week, project, category, billable, day, old, new)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Sets the hours of one day (0 = Monday)
+    pub fn set_day(&self, week: &str, project: &str, category: &str, day: usize, value: f64) -> Result<(), Box<dyn Error>> {
+        let old = self.row_hours(week, project, category)?.ok_or_else(|| format!("No row for {} in {}", project, week))?;
+        self.repo.set_day(week, project, category, day, value)?;
+        if old[day] == value { return Ok(()); }
+        self.log("edit", week, project, category, None, day, old[day], value)
+    }
+
+    pub fn delete_row(&self, week: &str, project: &str, category: &str) -> Result<(), Box<dyn Error>> {
+        let Some(old) = self.repo.entry(week, project, category)? else { return Ok(()); };
+        self.repo.delete_entry(week, project, category)?;
+        self.log_row("remove", week, project, category, old.billable, old.hours.map(|h| (h, 0.0)))
+    }
+
+    // Sets a row's own billable flag, or None to follow its project and category. Logged as a
+    // "billable" change on Monday with the flags as codes (see `flag_code`); the hours stay.
+    pub fn set_billable(&self, week: &str, project: &str, category: &str, billable: Option<bool>) -> Result<(), Box<dyn Error>> {
+        let old = self.repo.entry(week, project, category)?.ok_or_else(|| format!("No row for {} in {}", project, week))?;
+        self.repo.set_billable(week, project, category, billable)?;
+        if old.billable == billable { return Ok(()); }
+        self.record("billable", week, project, category, billable, 0, flag_code(old.billable), flag_code(billable))
+    }
+
+    // Records how a sync conflict was resolved, from the other device's value to the chosen
+    // one, without touching the hours; other devices pick it up as a normal change. It is kept
+    // even when the chosen value is theirs: it settles the cell.
+    pub fn resolve(&self, week: &str, project: &str, category: &str, day: usize, theirs: f64, chosen: f64) -> Result<(), Box<dyn Error>> {
+        self.log("resolve", week, project, category, None, day, theirs, chosen)
+    }
+
+    // Like `resolve`, for a conflict on a row's billable flag
+    pub fn resolve_billable(&self, week: &str, project: &str, category: &str, theirs: Option<bool>, chosen: Option<bool>) -> Result<(), Box<dyn Error>> {
+        self.record("resolve_billable", week, project, category, chosen, 0, flag_code(theirs), flag_code(chosen))
+    }
+
+    // Moves a batch between the undo (false) and the redo (true) side of the stack
+    pub fn mark_undone(&self, batch: i64, undone: bool) -> Result<(), Box<dyn Error>> {
+        self.tx.execute("UPDATE undo_stack SET undone = ?1 WHERE batch = ?2", params![undone, batch])?;
+        Ok(())
+    }
+
+    // Validates and commits the hours and the log. A change that breaks a rule returns
+    // `Rejected` and is rolled back; undo and redo restore earlier states and are not validated.
+    pub fn commit(mut self) -> Result<Committed, Box<dyn Error>> {
+        let mut warnings = Vec::new();
+        if self.source.undoable() {
+            let violations = validate::check_change(self.repo, &Rules::from_env()?, &self.cells.borrow())?;
+            let (errors, rest): (Vec<_>, Vec<_>) = violations.into_iter().partition(|v| v.severity == Severity::Error);
+            if !errors.is_empty() {
+                return Err(Box::new(Rejected(errors)));
+            }
+            warnings = rest;
+        }
+
+        let pushed = self.source.undoable() && self.logged.get();
+        if pushed {
+            // A new change makes the undone batches unreachable for redo
+            self.tx.execute("DELETE FROM undo_stack WHERE undone = 1", [])?;
+            self.tx.execute("INSERT INTO undo_stack (batch) VALUES (?1)", params![self.batch])?;
+        }
+        self.repo.commit()?;
+        self.pending.open = false;
+        self.tx.commit()?;
+        Ok(Committed { batch: pushed.then_some(self.batch), warnings })
+    }
+}
+
+// Changes of a row's billable flag rather than of its hours
+pub fn flag_action(action: &str) -> bool {
+    matches!(action, "billable" | "resolve_billable")
+}
+
+// A row's billable flag in the value columns of a flag change
+pub fn flag_code(billable: Option<bool>) -> f64 {
+    match billable {
+        Some(true) => 1.0,
+        Some(false) => 0.0,
+        None => -1.0,
+    }
+}
+
+pub fn code_flag(code: f64) -> Option<bool> {
+    if code < 0.0 { None } else { Some(code != 0.0) }
+}
+
+pub fn flag_label(tr: &Catalog, code: f64) -> &'static str {
+    match code_flag(code) {
+        Some(true) => tr.billable_column,
+        Some(false) => tr.non_billable_column,
+        None => tr.billable_follows,
+    }
+}
+
+// The change log can only grow: updates and deletes are rejected by triggers
+pub fn init_changes(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS changes (
+            id INTEGER PRIMARY KEY,
+            batch INTEGER NOT NULL,
+            changed_at TEXT NOT NULL,
+            source TEXT NOT NULL,
+            action TEXT NOT NULL,
+            week TEXT NOT NULL,
+            project TEXT NOT NULL,
+            day TEXT NOT NULL,
+            old_value REAL NOT NULL,
+            new_value REAL NOT NULL
+        )",
+        [],
+    )?;
+    // Changes logged before categories existed are uncategorised
+    let has_category: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('changes') WHERE name = 'category'", [], |row| row.get(0),
+    )?;
+    if !has_category {
+        conn.execute("ALTER TABLE changes ADD COLUMN category TEXT NOT NULL DEFAULT ''", [])?;
+    }
+    // The row's own billable flag of added and removed rows; NULL follows project and category
+    let has_billable: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('changes') WHERE name = 'billable'", [], |row| row.get(0),
+    )?;
+    if !has_billable {
+        conn.execute("ALTER TABLE changes ADD COLUMN billable INTEGER", [])?;
+    }
+    // Batches that can be undone (undone = 0) or redone (undone = 1), see undo.rs
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS undo_stack (
+            batch INTEGER PRIMARY KEY,
+            undone INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS changes_no_update BEFORE UPDATE ON changes
+         BEGIN SELECT RAISE(ABORT, 'the change log is append-only'); END",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS changes_no_delete BEFORE DELETE ON changes
+         BEGIN SELECT RAISE(ABORT, 'the change log is append-only'); END",
+        [],
+    )?;
+    Ok(())
+}
+
+// --- Function: Change history per week or project ---
+pub fn handle_history(conn: &Connection, tr: &Catalog, week: Option<&str>, project: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let mut stmt = conn.prepare(
+        "SELECT changed_at, source, action, week, project, day, old_value, new_value, category FROM changes
+         WHERE (?1 IS NULL OR week = ?1) AND (?2 IS NULL OR project = ?2)
+         ORDER BY id",
+    )?;
+    let rows = stmt.query_map(params![week, project], |row| {
+        Ok((
+            row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?, row.get::<_, String>(5)?, row.get::<_, f64>(6)?, row.get::<_, f64>(7)?,
+            row.get::<_, String>(8)?,
+        ))
+    })?;
+
+    println!("\n{}", tr.history_title);
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.changed_column), Cell::new(tr.source_column), Cell::new(tr.week_column), Cell::new(tr.date_column),
+        Cell::new(tr.project), Cell::new(tr.old_column), Cell::new(tr.new_column),
+    ]));
+
+    let mut count = 0;
+    for r in rows {
+        let (changed_at, source, action, week, project, day, old, new, category) = r?;
+        let (old, new) = if flag_action(&action) {
+            (flag_label(tr, old).to_string(), flag_label(tr, new).to_string())
+        } else {
+            (format_hours(old), format_hours(new))
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&changed_at),
+            Cell::new(&format!("{} ({})", source, action)),
+            Cell::new(&week),
+            Cell::new(&day_date(&week, &day).map(|d| d.to_string()).unwrap_or(day)),
+            Cell::new(&categories::row_label(tr, &project, &category)),
+            Cell::new(&old),
+            Cell::new(&new).style_spec("b"),
+        ]));
+        count += 1;
+    }
+
+    if count == 0 {
+        println!("{}", tr.no_changes);
+    } else {
+        table.printstd();
+    }
+    Ok(())
+}
+
+fn day_date(week: &str, day: &str) -> Option<NaiveDate> {
+    let index = DAY_COLUMNS.iter().position(|d| *d == day)?;
+    week_date(week, index)
+}
@@ -0,0 +1,147 @@
+use chrono::Local;
+use inquire::Confirm;
+use rusqlite::{backup::Progress, Connection, OpenFlags, MAIN_DB};
+use std::{env, error::Error, fs, path::{Path, PathBuf}};
+
+use crate::locale::Catalog;
+
+// --- Backups ---
+// Copies of timesheet.db made with SQLite's online backup API, so a backup is consistent
+// even while the database is open. Automatic backups go to BACKUP_DIR (default "backups")
+// and only the newest BACKUP_KEEP (default 10) are kept; manual backups are never rotated.
+// Backups are named after the database, so those of employees (timesheet_<code>.db) are
+// rotated separately.
+
+fn backup_dir() -> PathBuf {
+    PathBuf::from(env::var("BACKUP_DIR").ok().filter(|d| !d.trim().is_empty()).unwrap_or("backups".to_string()))
+}
+
+fn keep() -> Result<usize, Box<dyn Error>> {
+    match env::var("BACKUP_KEEP") {
+        Ok(v) if !v.trim().is_empty() => Ok(v.trim().parse().map_err(|_| format!("BACKUP_KEEP must be a number, got '{}'", v))?),
+        _ => Ok(10),
+    }
+}
+
+// "timesheet" for timesheet.db, "timesheet_jan" for an employee's database
+fn prefix(conn: &Connection) -> String {
+    conn.path()
+        .and_then(|p| Path::new(p).file_stem().map(|s| s.to_string_lossy().to_string()))
+        .filter(|s| !s.is_empty())
+        .unwrap_or("timesheet".to_string())
+}
+
+// e.g. backups/timesheet_2026-01-31_170502_startup.db
+fn backup_path(conn: &Connection, reason: &str) -> PathBuf {
+    backup_dir().join(format!("{}_{}_{}.db", prefix(conn), Local::now().format("%Y-%m-%d_%H%M%S"), reason))
+}
+
+// Backups of this database: the prefix directly followed by the date
+fn is_backup_of(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('_'))
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+// Runs PRAGMA integrity_check on a database file; the file must be a timesheet database.
+// Opened read-only, so a mistyped path is not created as an empty database.
+fn check_file(path: &Path) -> Result<(), Box<dyn Error>> {
+    if !path.exists() {
+        return Err(format!("{}: file not found", path.display()).into());
+    }
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut stmt = conn.prepare("PRAGMA integrity_check").map_err(|e| format!("{}: {}", path.display(), e))?;
+    let result = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<Result<Vec<_>, _>>()?;
+    if result != ["ok"] {
+        return Err(format!("{} failed the integrity check: {}", path.display(), result.join("; ")).into());
+    }
+    let has_hours: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'timesheets'", [], |row| row.get(0),
+    )?;
+    if !has_hours {
+        return Err(format!("{} is not a timesheet database", path.display()).into());
+    }
+    Ok(())
+}
+
+// Writes and verifies a backup; a backup that fails the check is removed again
+fn write_backup(conn: &Connection, path: &Path) -> Result<(), Box<dyn Error>> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    conn.backup(MAIN_DB, path, None)?;
+    if let Err(e) = check_file(path) {
+        fs::remove_file(path).ok();
+        return Err(e);
+    }
+    Ok(())
+}
+
+// Removes the oldest automatic backups beyond BACKUP_KEEP
+fn rotate(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    let prefix = prefix(conn);
+    let mut files: Vec<PathBuf> = fs::read_dir(backup_dir())?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            is_backup_of(name, &prefix) && name.ends_with(".db") && !name.ends_with("_manual.db")
+        })
+        .collect();
+    // The timestamp in the name sorts chronologically
+    files.sort();
+    let keep = keep()?;
+    if files.len() > keep {
+        for old in &files[..files.len() - keep] {
+            fs::remove_file(old)?;
+        }
+    }
+    Ok(())
+}
+
+// Automatic backup before a destructive action, e.g. "remove-project"
+pub fn auto(conn: &Connection, reason: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let path = backup_path(conn, reason);
+    write_backup(conn, &path)?;
+    rotate(conn)?;
+    Ok(path)
+}
+
+// Automatic backup at startup, at most once a day
+pub fn on_startup(conn: &Connection, tr: &Catalog) {
+    let today = format!("{}_{}_", prefix(conn), Local::now().format("%Y-%m-%d"));
+    let done_today = fs::read_dir(backup_dir())
+        .map(|entries| entries.filter_map(|e| e.ok()).any(|e| e.file_name().to_string_lossy().starts_with(&today)))
+        .unwrap_or(false);
+    if done_today { return; }
+
+    // A failed backup must not stop the command itself
+    if let Err(e) = auto(conn, "startup") {
+        println!("{}", Catalog::fill(tr.backup_failed, &[&e]));
+    }
+}
+
+// --- Function: Manual backup ---
+pub fn handle_backup(conn: &Connection, tr: &Catalog, to: Option<String>) -> Result<(), Box<dyn Error>> {
+    let path = to.map(PathBuf::from).unwrap_or_else(|| backup_path(conn, "manual"));
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+    write_backup(conn, &path)?;
+    println!("{}", Catalog::fill(tr.backup_written, &[&path.display()]));
+    Ok(())
+}
+
+// --- Function: Restore from a backup ---
+// The current data is backed up first, so a restore can itself be undone with another restore.
+pub fn handle_restore(conn: &mut Connection, tr: &Catalog, from: &str) -> Result<bool, Box<dyn Error>> {
+    let from = Path::new(from);
+    check_file(from)?;
+    if !Confirm::new(&Catalog::fill(tr.restore_confirm, &[&from.display()])).with_default(false).prompt()? {
+        return Ok(false);
+    }
+
+    let before = auto(conn, "before-restore")?;
+    conn.restore(MAIN_DB, from, None::<fn(Progress)>)?;
+    println!("{}", Catalog::fill(tr.restored, &[&from.display(), &before.display()]));
+    Ok(true)
+}
@@ -0,0 +1,24 @@
+use std::{env, error::Error};
+use timesheet_core::{Billing, Repository};
+
+use crate::{categories, locale::Catalog};
+
+// --- Billable Hours ---
+// All hours are billable unless marked otherwise: a project in `project`, a category with
+// NON_BILLABLE_CATEGORIES in .env (comma-separated, e.g. "training,sales"), or a single row
+// in `log`. Non-billable hours stay out of "Totaal facturabel" and get a row of their own.
+
+// The non-billable categories as stored (built-in ones by their code)
+pub fn non_billable_categories(tr: &Catalog) -> Vec<String> {
+    env::var("NON_BILLABLE_CATEGORIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| categories::parse(tr, name))
+        .collect()
+}
+
+pub fn load(repo: &dyn Repository, tr: &Catalog) -> Result<Billing, Box<dyn Error>> {
+    Billing::new(repo, &non_billable_categories(tr))
+}
@@ -0,0 +1,90 @@
+use clap::ValueEnum;
+use inquire::{Select, Text};
+use std::{env, error::Error};
+use timesheet_core::Repository;
+
+use crate::locale::{Catalog, Lang};
+
+// --- Activity Categories ---
+// Hours can be classified per row with an activity category, so a project can have a row
+// for development and one for meetings in the same week. The built-in categories are kept
+// as codes and shown in the language of the output; CATEGORIES in .env replaces the list
+// offered in `log` (comma-separated). Any other name can be typed and is kept as is.
+
+const BUILT_IN: [&str; 4] = ["development", "meeting", "travel", "support"];
+
+// Categories offered by the chooser, from CATEGORIES or the built-in ones
+pub fn configured() -> Vec<String> {
+    match env::var("CATEGORIES") {
+        Ok(list) if !list.trim().is_empty() => {
+            let mut categories: Vec<String> = Vec::new();
+            for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                if !categories.iter().any(|c| c == name) {
+                    categories.push(name.to_string());
+                }
+            }
+            categories
+        }
+        _ => BUILT_IN.iter().map(|c| c.to_string()).collect(),
+    }
+}
+
+// Name of a category in the catalog's language; other categories keep their own name
+pub fn label(tr: &Catalog, category: &str) -> String {
+    match category {
+        "" => tr.no_category,
+        "development" => tr.category_development,
+        "meeting" => tr.category_meeting,
+        "travel" => tr.category_travel,
+        "support" => tr.category_support,
+        other => other,
+    }
+    .to_string()
+}
+
+// "Acme" or "Acme / Overleg": how a project row is shown in tables and menus
+pub fn row_label(tr: &Catalog, project: &str, category: &str) -> String {
+    if category.is_empty() {
+        project.to_string()
+    } else {
+        format!("{} / {}", project, label(tr, category))
+    }
+}
+
+// A category typed in `log` or given with --category; a built-in one is stored as its code,
+// also when typed by name in any language ("Overleg" -> "meeting")
+pub fn parse(tr: &Catalog, name: &str) -> String {
+    let name = name.trim();
+    let catalogs: Vec<&Catalog> = std::iter::once(tr).chain(Lang::value_variants().iter().map(|l| l.catalog())).collect();
+    BUILT_IN
+        .iter()
+        .find(|c| c.eq_ignore_ascii_case(name) || catalogs.iter().any(|tr| label(tr, c).eq_ignore_ascii_case(name)))
+        .map(|c| c.to_string())
+        .unwrap_or(name.to_string())
+}
+
+// --- Function: Choose a category ---
+// The configured categories, then those already in use, then "other"; `current` is selected
+// first. Returns None when the prompt is cancelled.
+pub fn choose(repo: &dyn Repository, tr: &Catalog, current: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let mut categories = vec![String::new()];
+    let mut used: Vec<String> = repo.entries(None, None)?.into_iter().map(|e| e.category).collect();
+    used.extend(repo.templates()?.into_iter().map(|t| t.category));
+    used.sort();
+    for category in configured().iter().map(|c| parse(tr, c)).chain(used) {
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+
+    let mut options: Vec<String> = categories.iter().map(|c| label(tr, c)).collect();
+    options.push(tr.other_category.to_string());
+    let start = categories.iter().position(|c| c == current).unwrap_or(0);
+    let Ok(choice) = Select::new(tr.select_category, options).with_starting_cursor(start).raw_prompt() else { return Ok(None); };
+
+    if choice.index < categories.len() {
+        return Ok(Some(categories[choice.index].clone()));
+    }
+    let Ok(name) = Text::new(tr.category_name).prompt() else { return Ok(None); };
+    Ok(Some(parse(tr, &name)))
+}
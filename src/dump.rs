@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use std::{error::Error, io::Write};
+use timesheet_core::{day_records, Repository};
+
+use crate::format_hours;
+
+// --- Output Formats ---
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum DumpFormat {
+    Csv,
+    Json,
+    Ndjson,
+    Markdown,
+}
+
+// --- Function: Dump raw entries ---
+pub fn handle_dump(
+    repo: &dyn Repository,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    project: Option<&str>,
+    format: DumpFormat,
+) -> Result<(), Box<dyn Error>> {
+    if let (Some(f), Some(t)) = (from, to)
+        && f > t {
+        return Err(format!("--from ({}) is after --to ({})", f, t).into());
+    }
+
+    let records = day_records(repo, from, to, project)?;
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+
+    match format {
+        DumpFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            if records.is_empty() {
+                // Keep the header so consumers always see the same columns
+                writer.write_record(["date", "week", "project", "hours", "category"])?;
+            }
+            for record in &records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+        DumpFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &records)?;
+            writeln!(out)?;
+        }
+        DumpFormat::Ndjson => {
+            for record in &records {
+                serde_json::to_writer(&mut out, record)?;
+                writeln!(out)?;
+            }
+        }
+        DumpFormat::Markdown => {
+            writeln!(out, "| date | week | project | hours | category |")?;
+            writeln!(out, "|------|------|---------|------:|----------|")?;
+            for record in &records {
+                writeln!(
+                    out,
+                    "| {} | {} | {} | {} | {} |",
+                    record.date,
+                    record.week,
+                    record.project.replace('|', "\\|"),
+                    format_hours(record.hours),
+                    record.category.replace('|', "\\|")
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
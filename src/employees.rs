@@ -0,0 +1,288 @@
+use chrono::{Datelike, Local};
+use inquire::{Confirm, CustomType, Select, Text};
+use prettytable::{format, Cell, Row, Table};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{collections::BTreeMap, env, error::Error};
+use timesheet_core::{month_totals, EntryFilter};
+
+use crate::{billing, format_hours, init_db, layout::Layout, locale::Catalog, open_storage, share};
+
+// --- Employees ---
+// A team lead can keep the timesheets of several people. Without `--employee` the sheet is
+// your own, with the details from EMPLOYEE_* in .env. Every employee in the employees table
+// has their own database (timesheet_<code>.db) and, with plain-text storage, their own
+// STORAGE_DIR/<code>/, so hours, locks, undo, backups and sync never mix.
+
+pub const DATABASE: &str = "timesheet.db";
+
+#[derive(Debug, Clone)]
+pub struct Employee {
+    pub code: Option<String>, // None for your own sheet
+    pub name: String,
+    pub title: String,
+    pub phone: String,
+    pub signature: String, // image path, empty for none
+}
+
+impl Employee {
+    pub fn from_env() -> Employee {
+        Employee {
+            code: None,
+            name: env::var("EMPLOYEE_NAME").unwrap_or("John Doe".to_string()),
+            title: env::var("EMPLOYEE_TITLE").unwrap_or("Enterprise Architect".to_string()),
+            phone: env::var("EMPLOYEE_PHONE").unwrap_or("000000000".to_string()),
+            signature: String::new(),
+        }
+    }
+
+    // The employee selected with --employee, or yourself
+    pub fn select(conn: &Connection, tr: &Catalog, code: Option<&str>) -> Result<Employee, Box<dyn Error>> {
+        let Some(code) = code else { return Ok(Employee::from_env()); };
+        load(conn, code)?.ok_or_else(|| Catalog::fill(tr.unknown_employee, &[&code]).into())
+    }
+
+    // Database of the employee; your own sheet uses DATABASE
+    pub fn database(&self) -> Option<String> {
+        self.code.as_ref().map(|code| format!("timesheet_{}.db", code))
+    }
+
+    // Appended to exported file names, e.g. Urenstaat_2026_1_Acme_jan.xlsx
+    pub fn file_suffix(&self) -> String {
+        self.code.as_ref().map(|code| format!("_{}", code)).unwrap_or_default()
+    }
+
+    // Your own sheet is signed with the layout's signature, an employee only with their own
+    pub fn signature<'a>(&'a self, layout: &'a Layout) -> &'a str {
+        match self.code {
+            None => &layout.signature,
+            Some(_) => &self.signature,
+        }
+    }
+}
+
+impl std::fmt::Display for Employee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "{} ({})", self.name, code),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+pub fn init_employees(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS employees (
+            code TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            title TEXT NOT NULL DEFAULT '',
+            phone TEXT NOT NULL DEFAULT '',
+            signature TEXT NOT NULL DEFAULT ''
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn employee_from_row(row: &rusqlite::Row) -> rusqlite::Result<Employee> {
+    Ok(Employee { code: Some(row.get(0)?), name: row.get(1)?, title: row.get(2)?, phone: row.get(3)?, signature: row.get(4)? })
+}
+
+fn load(conn: &Connection, code: &str) -> Result<Option<Employee>, Box<dyn Error>> {
+    Ok(conn
+        .query_row("SELECT code, name, title, phone, signature FROM employees WHERE code = ?1", params![code], employee_from_row)
+        .optional()?)
+}
+
+fn employees(conn: &Connection) -> Result<Vec<Employee>, Box<dyn Error>> {
+    let mut stmt = conn.prepare("SELECT code, name, title, phone, signature FROM employees ORDER BY code")?;
+    let employees = stmt.query_map([], employee_from_row)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(employees)
+}
+
+// Codes end up in file names: a letter first, then letters, digits, - and _
+fn valid_code(code: &str) -> bool {
+    code.starts_with(|c: char| c.is_ascii_alphabetic())
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn save(conn: &Connection, employee: &Employee) -> Result<(), Box<dyn Error>> {
+    conn.execute(
+        "INSERT INTO employees (code, name, title, phone, signature) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(code) DO UPDATE SET
+            name = excluded.name, title = excluded.title, phone = excluded.phone, signature = excluded.signature",
+        params![employee.code, employee.name, employee.title, employee.phone, employee.signature],
+    )?;
+    Ok(())
+}
+
+// Asks for the details, pre-filled with the current ones
+fn prompt_details(tr: &Catalog, employee: &mut Employee) -> Result<(), Box<dyn Error>> {
+    employee.name = Text::new(tr.labels.employee_name).with_initial_value(&employee.name).prompt()?;
+    employee.title = Text::new(tr.labels.employee_title).with_initial_value(&employee.title).prompt()?;
+    employee.phone = Text::new(tr.labels.employee_phone).with_initial_value(&employee.phone).prompt()?;
+    employee.signature = Text::new(tr.employee_signature).with_initial_value(&employee.signature).prompt()?.trim().to_string();
+    Ok(())
+}
+
+// --- Function: Manage employees ---
+pub fn handle_employees(conn: &Connection, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+    loop {
+        println!("\n{}", tr.employees_title);
+
+        let team = employees(conn)?;
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+        table.set_titles(Row::new(vec![
+            Cell::new(tr.code_column),
+            Cell::new(tr.labels.employee_name),
+            Cell::new(tr.labels.employee_title),
+            Cell::new(tr.labels.employee_phone),
+            Cell::new(tr.signature_column),
+        ]));
+        for e in &team {
+            table.add_row(Row::new(vec![
+                Cell::new(e.code.as_deref().unwrap_or_default()),
+                Cell::new(&e.name),
+                Cell::new(&e.title),
+                Cell::new(&e.phone),
+                Cell::new(&e.signature),
+            ]));
+        }
+        table.printstd();
+
+        match Select::new(tr.action, vec![tr.create_new, tr.edit_existing, tr.delete, tr.exit]).prompt() {
+            Ok(a) if a == tr.create_new => {
+                let code = Text::new(tr.employee_code).prompt().unwrap_or_default().trim().to_string();
+                if code.is_empty() { continue; }
+                if !valid_code(&code) {
+                    println!("{}", Catalog::fill(tr.invalid_employee_code, &[&code]));
+                    continue;
+                }
+                if load(conn, &code)?.is_some() {
+                    println!("{}", Catalog::fill(tr.employee_exists, &[&code]));
+                    continue;
+                }
+                let mut employee = Employee { code: Some(code), name: String::new(), title: String::new(), phone: String::new(), signature: String::new() };
+                if prompt_details(tr, &mut employee).is_ok() {
+                    save(conn, &employee)?;
+                }
+            },
+            Ok(a) if a == tr.edit_existing => {
+                if team.is_empty() { continue; }
+                let Ok(mut employee) = Select::new(tr.select_employee, team).prompt() else { continue; };
+                if prompt_details(tr, &mut employee).is_ok() {
+                    save(conn, &employee)?;
+                }
+            },
+            Ok(a) if a == tr.delete => {
+                if team.is_empty() { continue; }
+                // Only the details are removed; the employee's database and files are kept
+                if let Ok(employee) = Select::new(tr.select_employee, team).prompt()
+                    && Confirm::new(tr.are_you_sure).prompt().unwrap_or(false) {
+                    conn.execute("DELETE FROM employees WHERE code = ?1", params![employee.code])?;
+                }
+            },
+            _ => break,
+        }
+    }
+    Ok(())
+}
+
+// (all, billable) hours per project
+type ProjectHours = BTreeMap<String, (f64, f64)>;
+
+// --- Function: Team month report ---
+// Hours per employee and project for one month, with totals per employee, per project and for
+// the team, and the share of billable hours
+pub fn handle_team(conn: &Connection, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+    let now = Local::now();
+    let default_year = now.year();
+    let default_month = if now.month() == 1 { 12 } else { now.month() - 1 };
+
+    println!("\n{}", tr.team_title);
+    let year = CustomType::<i32>::new(tr.year).with_default(default_year).prompt()?;
+    let month = CustomType::<u32>::new(tr.month).with_default(default_month).prompt()?;
+
+    let mut rows: Vec<(Employee, ProjectHours)> = Vec::new();
+    for employee in std::iter::once(Employee::from_env()).chain(employees(conn)?) {
+        let opened;
+        let employee_conn = match employee.database() {
+            Some(path) => {
+                opened = Connection::open(path)?;
+                init_db(&opened)?;
+                &opened
+            }
+            None => conn,
+        };
+        let storage = open_storage(employee_conn, &employee)?;
+        let billing = billing::load(storage.as_ref(), tr)?;
+        let billable = month_totals(&EntryFilter::billable(storage.as_ref(), &billing, true), year, month)?;
+        let hours: ProjectHours = month_totals(storage.as_ref(), year, month)?
+            .into_iter()
+            .filter(|(_, total)| *total != 0.0)
+            .map(|(project, total)| {
+                let billable = billable.get(&project).copied().unwrap_or(0.0);
+                (project, (total, billable))
+            })
+            .collect();
+        // Your own sheet is only part of the team when it has hours
+        if employee.code.is_some() || !hours.is_empty() {
+            rows.push((employee, hours));
+        }
+    }
+
+    if rows.iter().all(|(_, hours)| hours.is_empty()) {
+        println!("{}", Catalog::fill(tr.no_data, &[&month, &year]));
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![Cell::new(tr.employee_column), Cell::new(tr.project), Cell::new(tr.tot), Cell::new(tr.billable_share_column)]));
+
+    // (all, billable) hours
+    let mut project_totals: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+    let mut team_total = (0.0, 0.0);
+    for (employee, hours) in &rows {
+        let mut employee_total = (0.0, 0.0);
+        for (project, &(total, billable)) in hours {
+            table.add_row(Row::new(vec![
+                Cell::new(&employee.to_string()),
+                Cell::new(project),
+                Cell::new(&format_hours(total)),
+                Cell::new(&share(billable, total)),
+            ]));
+            let totals = project_totals.entry(project).or_insert((0.0, 0.0));
+            totals.0 += total;
+            totals.1 += billable;
+            employee_total.0 += total;
+            employee_total.1 += billable;
+        }
+        table.add_row(Row::new(vec![
+            Cell::new(&employee.to_string()).style_spec("b"),
+            Cell::new(tr.total).style_spec("b"),
+            Cell::new(&format_hours(employee_total.0)).style_spec("b"),
+            Cell::new(&share(employee_total.1, employee_total.0)).style_spec("b"),
+        ]));
+        team_total.0 += employee_total.0;
+        team_total.1 += employee_total.1;
+    }
+    for (project, (total, billable)) in &project_totals {
+        table.add_row(Row::new(vec![
+            Cell::new(tr.total).style_spec("b"),
+            Cell::new(project).style_spec("b"),
+            Cell::new(&format_hours(*total)).style_spec("b"),
+            Cell::new(&share(*billable, *total)).style_spec("b"),
+        ]));
+    }
+    table.add_row(Row::new(vec![
+        Cell::new(tr.total).style_spec("b"),
+        Cell::new(""),
+        Cell::new(&format_hours(team_total.0)).style_spec("bub"),
+        Cell::new(&share(team_total.1, team_total.0)).style_spec("b"),
+    ]));
+
+    println!("\n{}", Catalog::fill(tr.team_report, &[&month, &year]));
+    table.printstd();
+    Ok(())
+}
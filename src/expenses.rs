@@ -0,0 +1,313 @@
+use chrono::{Datelike, Local, NaiveDate};
+use inquire::{Confirm, CustomType, Select, Text};
+use prettytable::{format, Cell, Row, Table};
+use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, env, error::Error, fmt, fs, io::Write, path::{Path, PathBuf}};
+use timesheet_core::{month_bounds, Repository};
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{choose_project, employees::Employee, lock, locale::Catalog};
+
+// --- Expenses & Receipts ---
+// Expenses (date, project, description, amount incl. VAT) with their receipt. A receipt is
+// copied into RECEIPT_DIR (default "receipts") under the SHA-256 of its content, e.g.
+// receipts/2026-10/3f2a….pdf, so the same file is kept once and a receipt that was changed
+// or removed afterwards is noticed. Employees keep their receipts in a subfolder named after
+// their code. The expenses fill the expense rows of the export, and `export --zip` bundles
+// the sheet with the receipts. Expenses are not in the change log nor in the hours hash of a
+// locked period: a lock refuses new changes, but does not show earlier ones.
+
+const RECEIPT_TYPES: [&str; 4] = ["pdf", "jpg", "jpeg", "png"];
+
+#[derive(Debug, Clone)]
+pub struct Expense {
+    id: i64,
+    pub date: NaiveDate,
+    pub project: String,
+    pub description: String,
+    pub amount: f64, // incl. VAT, as on the receipt
+    receipt: String, // path inside the archive, empty without a receipt
+    receipt_hash: String,
+    label: String, // as shown in the menus
+}
+
+impl fmt::Display for Expense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptState {
+    Ok,
+    None,
+    Missing, // the archived file is gone
+    Changed, // the archived file no longer matches its hash
+}
+
+impl ReceiptState {
+    fn label(self, tr: &Catalog) -> &'static str {
+        match self {
+            ReceiptState::Ok => tr.receipt_ok,
+            ReceiptState::None => tr.receipt_none,
+            ReceiptState::Missing => tr.receipt_missing,
+            ReceiptState::Changed => tr.receipt_changed,
+        }
+    }
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+// The receipt folder of your own sheet or of an employee
+pub struct Archive {
+    dir: PathBuf,
+}
+
+impl Archive {
+    pub fn new(employee: &Employee) -> Archive {
+        let dir = PathBuf::from(env::var("RECEIPT_DIR").unwrap_or("receipts".to_string()));
+        Archive { dir: match &employee.code { Some(code) => dir.join(code), None => dir } }
+    }
+
+    // Copies a receipt into the month's folder under its hash; returns the path inside the
+    // archive and the hash. An archived copy that no longer matches is replaced, so attaching
+    // the original again repairs a changed receipt.
+    fn store(&self, source: &Path, date: NaiveDate) -> Result<(String, String), Box<dyn Error>> {
+        let bytes = fs::read(source).map_err(|e| format!("{}: {}", source.display(), e))?;
+        let hash = sha256(&bytes);
+        let extension = extension(source).unwrap_or_default();
+        let receipt = format!("{}/{}.{}", date.format("%Y-%m"), hash, extension);
+
+        let target = self.dir.join(&receipt);
+        if fs::read(&target).map(|b| sha256(&b)).ok().as_ref() != Some(&hash) {
+            fs::create_dir_all(target.parent().unwrap_or(&self.dir))?;
+            fs::write(&target, &bytes)?;
+        }
+        Ok((receipt, hash))
+    }
+
+    pub fn state(&self, expense: &Expense) -> ReceiptState {
+        if expense.receipt.is_empty() {
+            return ReceiptState::None;
+        }
+        match fs::read(self.dir.join(&expense.receipt)) {
+            Ok(bytes) if sha256(&bytes) == expense.receipt_hash => ReceiptState::Ok,
+            Ok(_) => ReceiptState::Changed,
+            Err(_) => ReceiptState::Missing,
+        }
+    }
+}
+
+// Lower-case extension of an accepted receipt type
+fn extension(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    RECEIPT_TYPES.contains(&extension.as_str()).then_some(extension)
+}
+
+fn euro(amount: f64) -> String {
+    format!("€ {:.2}", amount)
+}
+
+pub fn init_expenses(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS expenses (
+            id INTEGER PRIMARY KEY,
+            date TEXT NOT NULL,
+            project TEXT NOT NULL,
+            description TEXT NOT NULL,
+            amount REAL NOT NULL,
+            receipt TEXT NOT NULL DEFAULT '',
+            receipt_hash TEXT NOT NULL DEFAULT ''
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+// Expenses of a month, optionally of one project, by date
+pub fn month_expenses(conn: &Connection, year: i32, month: u32, project: Option<&str>) -> Result<Vec<Expense>, Box<dyn Error>> {
+    let (first, last) = month_bounds(year, month)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, date, project, description, amount, receipt, receipt_hash FROM expenses
+         WHERE date BETWEEN ?1 AND ?2 AND (?3 IS NULL OR project = ?3)
+         ORDER BY date, id",
+    )?;
+    let rows = stmt.query_map(params![first.to_string(), last.to_string(), project], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?))
+    })?;
+
+    let mut expenses = Vec::new();
+    for row in rows {
+        let (id, date, project, description, amount, receipt, receipt_hash) = row?;
+        let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")?;
+        let label = format!("{} {}: {} ({})", date.format("%d-%m-%Y"), project, description, euro(amount));
+        expenses.push(Expense { id, date, project, description, amount, receipt, receipt_hash, label });
+    }
+    Ok(expenses)
+}
+
+fn print_expenses(tr: &Catalog, archive: &Archive, expenses: &[Expense]) {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.date_column),
+        Cell::new(tr.project),
+        Cell::new(tr.description_column),
+        Cell::new(tr.amount_column),
+        Cell::new(tr.receipt_column),
+        Cell::new(tr.check_column),
+    ]));
+    for expense in expenses {
+        table.add_row(Row::new(vec![
+            Cell::new(&expense.date.format("%d-%m-%Y").to_string()),
+            Cell::new(&expense.project),
+            Cell::new(&expense.description),
+            Cell::new(&euro(expense.amount)),
+            Cell::new(&expense.receipt),
+            Cell::new(archive.state(expense).label(tr)),
+        ]));
+    }
+    let total: f64 = expenses.iter().map(|e| e.amount).sum();
+    table.add_row(Row::new(vec![
+        Cell::new(tr.total), Cell::new(""), Cell::new(""), Cell::new(&euro(total)), Cell::new(""), Cell::new(""),
+    ]));
+    table.printstd();
+}
+
+// Asks for a receipt file and archives it for the expense; nothing happens when no path is
+// given or the file type is not accepted
+fn attach(conn: &Connection, tr: &Catalog, archive: &Archive, id: i64, date: NaiveDate) -> Result<(), Box<dyn Error>> {
+    let path = Text::new(tr.receipt_path).prompt().unwrap_or_default();
+    // Paths dragged into a terminal are often quoted
+    let path = path.trim().trim_matches(|c| c == '\'' || c == '"');
+    if path.is_empty() {
+        return Ok(());
+    }
+    if extension(Path::new(path)).is_none() {
+        println!("{}", Catalog::fill(tr.invalid_receipt, &[&path]));
+        return Ok(());
+    }
+    let (receipt, hash) = archive.store(Path::new(path), date)?;
+    conn.execute("UPDATE expenses SET receipt = ?1, receipt_hash = ?2 WHERE id = ?3", params![receipt, hash, id])?;
+    println!("{}", Catalog::fill(tr.receipt_archived, &[&receipt]));
+    Ok(())
+}
+
+// --- Function: Register expenses ---
+pub fn handle_expenses(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+    let today = Local::now().date_naive();
+    let year = CustomType::<i32>::new(tr.year).with_default(today.year()).prompt()?;
+    let month = CustomType::<u32>::new(tr.month).with_default(today.month()).prompt()?;
+    let (first, last) = month_bounds(year, month)?;
+    let archive = Archive::new(employee);
+
+    loop {
+        println!("\n{}", Catalog::fill(tr.expenses_title, &[&month, &year]));
+        let expenses = month_expenses(conn, year, month, None)?;
+        if expenses.is_empty() {
+            println!("{}", Catalog::fill(tr.no_expenses, &[&month, &year]));
+        } else {
+            print_expenses(tr, &archive, &expenses);
+        }
+
+        match Select::new(tr.action, vec![tr.add_expense, tr.attach_receipt, tr.remove_expense, tr.exit]).prompt() {
+            Ok(a) if a == tr.add_expense => {
+                let Ok(date) = CustomType::<NaiveDate>::new(tr.trip_date).with_default(today.clamp(first, last)).prompt() else { continue; };
+                let Some(project) = choose_project(repo, tr)? else { continue; };
+                if lock::refuse_month(conn, tr, &project, date)? { continue; }
+                let Ok(description) = Text::new(tr.expense_description).prompt() else { continue; };
+                let Ok(amount) = CustomType::<f64>::new(tr.expense_amount).prompt() else { continue; };
+                if amount <= 0.0 {
+                    println!("{}", tr.invalid_amount);
+                    continue;
+                }
+                conn.execute(
+                    "INSERT INTO expenses (date, project, description, amount) VALUES (?1, ?2, ?3, ?4)",
+                    params![date.to_string(), project, description.trim(), amount],
+                )?;
+                attach(conn, tr, &archive, conn.last_insert_rowid(), date)?;
+            },
+            Ok(a) if a == tr.attach_receipt => {
+                if expenses.is_empty() { continue; }
+                let Ok(expense) = Select::new(tr.select_expense, expenses).prompt() else { continue; };
+                if lock::refuse_month(conn, tr, &expense.project, expense.date)? { continue; }
+                attach(conn, tr, &archive, expense.id, expense.date)?;
+            },
+            Ok(a) if a == tr.remove_expense => {
+                if expenses.is_empty() { continue; }
+                let Ok(expense) = Select::new(tr.select_expense, expenses).prompt() else { continue; };
+                if lock::refuse_month(conn, tr, &expense.project, expense.date)? { continue; }
+                // The archived receipt is kept; another expense may use the same file
+                if Confirm::new(tr.are_you_sure).prompt().unwrap_or(false) {
+                    conn.execute("DELETE FROM expenses WHERE id = ?1", params![expense.id])?;
+                }
+            },
+            _ => break,
+        }
+    }
+    Ok(())
+}
+
+// --- Function: Receipts of a month ---
+// Lists the expenses with their archived receipt; returns true when one has no valid receipt
+pub fn handle_receipts(conn: &Connection, employee: &Employee, tr: &Catalog) -> Result<bool, Box<dyn Error>> {
+    let now = Local::now();
+    let (default_year, default_month) = if now.month() == 1 { (now.year() - 1, 12) } else { (now.year(), now.month() - 1) };
+    let year = CustomType::<i32>::new(tr.year).with_default(default_year).prompt()?;
+    let month = CustomType::<u32>::new(tr.month).with_default(default_month).prompt()?;
+
+    println!("\n{}", Catalog::fill(tr.receipts_title, &[&month, &year]));
+    let expenses = month_expenses(conn, year, month, None)?;
+    if expenses.is_empty() {
+        println!("{}", Catalog::fill(tr.no_expenses, &[&month, &year]));
+        return Ok(false);
+    }
+    let archive = Archive::new(employee);
+    print_expenses(tr, &archive, &expenses);
+
+    let incomplete = expenses.iter().filter(|e| archive.state(e) != ReceiptState::Ok).count();
+    if incomplete > 0 {
+        println!("\n{}", Catalog::fill(tr.receipts_incomplete, &[&incomplete]));
+    }
+    Ok(incomplete > 0)
+}
+
+// --- Export bundle ---
+// Zips the exported files (with their signed manifests) and the receipts of the expenses,
+// numbered per project like the expense rows of its sheet. Expenses without a valid receipt
+// are reported.
+pub fn write_bundle(tr: &Catalog, employee: &Employee, filename: &str, files: &[String], expenses: &[Expense]) -> Result<(), Box<dyn Error>> {
+    let archive = Archive::new(employee);
+    let mut zip = ZipWriter::new(fs::File::create(filename)?);
+    let options = SimpleFileOptions::default();
+
+    let signatures: Vec<String> = files.iter().map(|f| format!("{}.sig", f)).filter(|f| Path::new(f).exists()).collect();
+    for file in files.iter().chain(&signatures) {
+        let name = Path::new(file).file_name().and_then(|n| n.to_str()).unwrap_or(file);
+        zip.start_file(name, options)?;
+        zip.write_all(&fs::read(file)?)?;
+    }
+
+    let mut rows: BTreeMap<&str, usize> = BTreeMap::new();
+    for expense in expenses {
+        let row = rows.entry(&expense.project).or_insert(0);
+        *row += 1;
+        let state = archive.state(expense);
+        if state != ReceiptState::Ok {
+            println!("{}", Catalog::fill(tr.receipt_warning, &[&expense.description, &expense.date.format("%d-%m-%Y"), &state.label(tr)]));
+            continue;
+        }
+        let extension = Path::new(&expense.receipt).extension().and_then(|e| e.to_str()).unwrap_or_default();
+        let name: String = format!("{:02}_{}_{}_{}", row, expense.date, expense.project, expense.description)
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        zip.start_file(format!("receipts/{}.{}", name, extension), options)?;
+        zip.write_all(&fs::read(archive.dir.join(&expense.receipt))?)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
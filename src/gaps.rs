@@ -0,0 +1,91 @@
+use chrono::{Datelike, NaiveDate};
+use prettytable::{format, Cell, Row, Table};
+use std::{env, error::Error};
+use timesheet_core::{daily_totals, Repository};
+
+use crate::{format_hours, locale::Catalog, validate::Rules};
+
+// --- Contract Hours ---
+// CONTRACT_HOURS in .env: the hours per weekday, Monday first (default "8,8,8,8,8,0,0").
+// LEAVE: days off, comma separated dates or ranges ("2026-07-20..2026-08-07").
+struct Contract {
+    hours: [f64; 7],
+    leave: Vec<(NaiveDate, NaiveDate)>,
+}
+
+impl Contract {
+    fn from_env() -> Result<Contract, Box<dyn Error>> {
+        let mut hours = [8.0, 8.0, 8.0, 8.0, 8.0, 0.0, 0.0];
+        if let Ok(value) = env::var("CONTRACT_HOURS")
+            && !value.trim().is_empty()
+        {
+            let parts: Vec<&str> = value.split(',').map(str::trim).collect();
+            if parts.len() != 7 {
+                return Err(format!("CONTRACT_HOURS needs 7 values (Monday to Sunday), got '{}'", value).into());
+            }
+            for (i, part) in parts.iter().enumerate() {
+                hours[i] = part.parse().map_err(|_| format!("CONTRACT_HOURS: invalid number '{}'", part))?;
+            }
+        }
+
+        let date = |d: &str| NaiveDate::parse_from_str(d.trim(), "%Y-%m-%d").map_err(|_| format!("LEAVE: invalid date '{}'", d));
+        let mut leave = Vec::new();
+        for item in env::var("LEAVE").unwrap_or_default().split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match item.split_once("..") {
+                Some((from, to)) => leave.push((date(from)?, date(to)?)),
+                None => leave.push((date(item)?, date(item)?)),
+            }
+        }
+        Ok(Contract { hours, leave })
+    }
+
+    fn on_leave(&self, date: NaiveDate) -> bool {
+        self.leave.iter().any(|(from, to)| *from <= date && date <= *to)
+    }
+}
+
+// --- Function: List workdays with missing hours ---
+// Returns whether any gaps were found, so `gaps` can exit with a non-zero status.
+pub fn handle_gaps(repo: &dyn Repository, tr: &Catalog, from: NaiveDate, to: NaiveDate) -> Result<bool, Box<dyn Error>> {
+    if from > to {
+        return Err(format!("--from ({}) is after --to ({})", from, to).into());
+    }
+    let contract = Contract::from_env()?;
+    let rules = Rules::from_env()?;
+
+    let logged = daily_totals(repo, from, to)?;
+
+    println!("\n{}", Catalog::fill(tr.gaps_title, &[&from, &to]));
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.date_column), Cell::new(tr.day_column), Cell::new(tr.logged_column),
+        Cell::new(tr.contract_column), Cell::new(tr.missing_column),
+    ]));
+
+    let (mut days, mut missing_total) = (0, 0.0);
+    for date in from.iter_days().take_while(|d| *d <= to) {
+        let expected = contract.hours[date.weekday().num_days_from_monday() as usize];
+        if expected == 0.0 || rules.holiday(date).is_some() || contract.on_leave(date) { continue; }
+
+        let hours = logged.get(&date).copied().unwrap_or(0.0);
+        if hours >= expected { continue; }
+        table.add_row(Row::new(vec![
+            Cell::new(&date.to_string()),
+            Cell::new(tr.weekday(date.weekday())),
+            Cell::new(&format_hours(hours)),
+            Cell::new(&format_hours(expected)),
+            Cell::new(&format_hours(expected - hours)).style_spec("b"),
+        ]));
+        days += 1;
+        missing_total += expected - hours;
+    }
+
+    if days == 0 {
+        println!("{}", tr.no_gaps);
+    } else {
+        table.printstd();
+        println!("{}", Catalog::fill(tr.gaps_total, &[&days, &format_hours(missing_total)]));
+    }
+    Ok(days > 0)
+}
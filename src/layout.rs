@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use std::{env, error::Error, fs};
+
+use crate::locale::Lang;
+
+// --- Export Layout ---
+// Everything agency specific about the Urenstaat. Every field has a default, so a
+// layout file only needs to contain what differs from the standard form (in the
+// selected language).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Layout {
+    pub font_name: String,
+    pub font_size: f64,
+    pub title_font_size: f64,
+    pub header_color: String, // hex RGB, e.g. "F28E00"
+    /// Row of the calendar header (weekday names); the grid, expenses and signatures follow it
+    pub calendar_row: u32,
+    /// Lines written below the logo
+    pub address_lines: Vec<String>,
+    /// Image paths; leave empty to omit the image
+    pub logo: String,
+    pub signature: String,
+    pub blocks: Blocks,
+    pub labels: Labels,
+    /// Language of the weekday/month names; the labels default to the same language
+    #[serde(skip)]
+    pub lang: Lang,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Blocks {
+    pub employee: bool,
+    pub assignment: bool,
+    pub period: bool,
+    pub expenses: bool,
+    /// Trips of the month with their mileage allowance (see `travel`); left out without trips
+    pub mileage: bool,
+    pub signatures: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Labels {
+    pub title: String,
+    pub employee_name: String,
+    pub employee_title: String,
+    pub employee_phone: String,
+    pub client: String,
+    pub role: String,
+    pub project_name: String,
+    pub project_number: String,
+    pub month: String,
+    pub year: String,
+    pub fill_date: String,
+    pub worked_hours: String,
+    pub total: String,
+    pub total_billable: String,
+    pub non_billable: String,
+    pub expenses: String,
+    pub expense_date: String,
+    pub expense_description: String,
+    pub expense_excl_vat: String,
+    pub expense_vat: String,
+    pub expense_incl_vat: String,
+    pub expense_total: String,
+    pub mileage: String,
+    pub mileage_from: String,
+    pub mileage_to: String,
+    pub mileage_mode: String,
+    pub mileage_km: String,
+    pub mileage_rate: String,
+    pub mileage_amount: String,
+    pub sign_client: String,
+    pub sign_employee: String,
+    pub sign_date: String,
+    pub signature_client: String,
+    pub signature_employee: String,
+    /// Written in the client's signature box of an approved month, e.g. "Approved by {} on {}"
+    pub approved_by: String,
+    pub summary: String,
+    pub project: String,
+    pub week: String,
+    pub description: String,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            font_name: "Verdana".to_string(),
+            font_size: 10.0,
+            title_font_size: 14.0,
+            header_color: "F28E00".to_string(),
+            calendar_row: 14,
+            address_lines: vec!["Hoeksekade 160".to_string(), "2661, JL Bergschenhoek".to_string()],
+            logo: "logo.jpg".to_string(),
+            signature: "signature.png".to_string(),
+            blocks: Blocks::default(),
+            labels: Labels::default(),
+            lang: Lang::Nl,
+        }
+    }
+}
+
+impl Default for Blocks {
+    fn default() -> Self {
+        Blocks { employee: true, assignment: true, period: true, expenses: true, mileage: true, signatures: true }
+    }
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Lang::Nl.catalog().export_labels()
+    }
+}
+
+// Row numbers of the blocks below the calendar, derived from `calendar_row` and the enabled blocks
+#[derive(Debug, Clone, Copy)]
+pub struct Rows {
+    pub cal: u32,
+    pub hours: u32,
+    pub total: u32, // follows the hour lines
+    pub expenses: Option<u32>, // title row; header, a line per expense (at least 4) and total follow
+    pub mileage: Option<u32>, // title row; header, a line per trip and total follow
+    pub signatures: Option<u32>,
+    pub last: u32, // last row of the print area
+}
+
+impl Layout {
+    pub fn for_lang(lang: Lang) -> Layout {
+        Layout { labels: lang.catalog().export_labels(), lang, ..Layout::default() }
+    }
+
+    // Explicit path first, then EXPORT_LAYOUT from .env, otherwise the built-in layout
+    pub fn load(path: Option<&str>, lang: Lang) -> Result<Layout, Box<dyn Error>> {
+        let path = match path {
+            Some(p) => p.to_string(),
+            None => match env::var("EXPORT_LAYOUT") {
+                Ok(p) if !p.is_empty() => p,
+                _ => return Ok(Layout::for_lang(lang)),
+            },
+        };
+
+        let content = fs::read_to_string(&path).map_err(|e| format!("Layout {}: {}", path, e))?;
+        let overrides: toml::Table = toml::from_str(&content).map_err(|e| format!("Layout {}: {}", path, e))?;
+
+        // Apply the file on top of the defaults of the selected language
+        let mut merged = toml::Table::try_from(Layout::for_lang(lang))?;
+        merge_tables(&mut merged, overrides);
+        let mut layout: Layout = toml::Value::Table(merged)
+            .try_into()
+            .map_err(|e| format!("Layout {}: {}", path, e))?;
+        layout.lang = lang;
+        layout.header_rgb()?;
+        if layout.calendar_row < 12 {
+            return Err(format!("Layout {}: calendar_row must be 12 or higher to stay below the header block", path).into());
+        }
+        Ok(layout)
+    }
+
+    pub fn header_rgb(&self) -> Result<u32, Box<dyn Error>> {
+        let hex = self.header_color.trim_start_matches('#');
+        u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.len() == 6)
+            .ok_or_else(|| format!("Invalid header_color '{}', expected RRGGBB", self.header_color).into())
+    }
+
+    // Row positions for a given number of hour lines (a line per activity on the monthly sheet,
+    // per project on the weekly one), expense lines and trips in the mileage block
+    pub fn rows_with_lines(&self, lines: u32, expenses: u32, trips: u32) -> Rows {
+        let cal = self.calendar_row;
+        let hours = cal + 2;
+        let total = hours + lines;
+        let mut next = total + 3;
+
+        let expenses = self.blocks.expenses.then(|| {
+            let row = next;
+            next = row + expenses.max(4) + 5;
+            row
+        });
+        let mileage = (self.blocks.mileage && trips > 0).then(|| {
+            let row = next;
+            next = row + trips + 5;
+            row
+        });
+        let signatures = self.blocks.signatures.then_some(next);
+        let last = match signatures {
+            Some(sign_row) => sign_row + 12,
+            None => next + 3,
+        };
+
+        Rows { cal, hours, total, expenses, mileage, signatures, last }
+    }
+}
+
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(table)) => merge_tables(base_table, table),
+            (_, value) => { base.insert(key, value); }
+        }
+    }
+}
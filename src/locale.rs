@@ -0,0 +1,1196 @@
+use chrono::Weekday;
+use clap::ValueEnum;
+use std::env;
+
+use crate::layout::Labels;
+
+// --- Languages ---
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    #[default]
+    Nl,
+    En,
+    De,
+}
+
+impl Lang {
+    pub fn code(self) -> &'static str {
+        match self { Lang::Nl => "nl", Lang::En => "en", Lang::De => "de" }
+    }
+
+    pub fn parse(code: &str) -> Option<Lang> {
+        Lang::from_str(code.trim(), true).ok()
+    }
+
+    // TIMESHEET_LANG from .env, if set to a known language
+    pub fn from_env() -> Option<Lang> {
+        env::var("TIMESHEET_LANG").ok().and_then(|code| Lang::parse(&code))
+    }
+
+    pub fn catalog(self) -> &'static Catalog {
+        match self { Lang::Nl => &NL, Lang::En => &EN, Lang::De => &DE }
+    }
+}
+
+impl std::fmt::Display for Lang {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.catalog().language)
+    }
+}
+
+// --- Translation Catalog ---
+// Strings containing "{}" are filled in with `Catalog::fill`.
+pub struct Catalog {
+    pub language: &'static str,
+    pub months: [&'static str; 12],
+    pub weekdays: [&'static str; 7],       // terminal tables, e.g. "Mon"
+    pub weekdays_short: [&'static str; 7], // export calendar, e.g. "Ma"
+    pub weekday_initials: [&'static str; 7],
+
+    // Terminal output
+    pub template_title: &'static str,
+    pub timesheet_title: &'static str,
+    pub month_title: &'static str,
+    pub projects_title: &'static str,
+    pub project: &'static str,
+    pub total: &'static str,
+    pub tot: &'static str,
+    pub language_column: &'static str,
+    pub action: &'static str,
+    pub create_new: &'static str,
+    pub edit_existing: &'static str,
+    pub delete: &'static str,
+    pub exit: &'static str,
+    pub project_name: &'static str,
+    pub select_project: &'static str,
+    pub are_you_sure: &'static str,
+    pub enter_hours: &'static str,
+    pub enter_week: &'static str,
+    pub no_entries: &'static str,
+    pub load_defaults: &'static str,
+    pub edit_day: &'static str,
+    pub add_project: &'static str,
+    pub remove_project: &'static str,
+    pub select_day: &'static str,
+    pub hours_for: &'static str,
+    pub remove: &'static str,
+    pub year: &'static str,
+    pub month: &'static str,
+    pub no_data: &'static str,
+    pub report: &'static str,
+    pub no_projects_export: &'static str,
+    pub select_project_export: &'static str,
+    pub select_projects_week: &'static str,
+    pub filename: &'static str,
+    pub file_generated: &'static str,
+    pub set_language: &'static str,
+    pub select_language: &'static str,
+    pub default_language: &'static str,
+    pub period_submitted: &'static str,
+    pub period_locked: &'static str,
+    pub day_locked: &'static str,
+    pub already_locked: &'static str,
+    pub no_locked_periods: &'static str,
+    pub select_period: &'static str,
+    pub unlock_reason: &'static str,
+    pub period_unlocked: &'static str,
+    pub periods_title: &'static str,
+    pub unlocks_title: &'static str,
+    pub state_column: &'static str,
+    pub since_column: &'static str,
+    pub period_column: &'static str,
+    pub unlocked_column: &'static str,
+    pub hours_column: &'static str,
+    pub reason_column: &'static str,
+    pub state_submitted: &'static str,
+    pub state_locked: &'static str,
+    pub hours_unchanged: &'static str,
+    pub hours_changed: &'static str,
+    pub history_title: &'static str,
+    pub no_changes: &'static str,
+    pub changed_column: &'static str,
+    pub source_column: &'static str,
+    pub week_column: &'static str,
+    pub date_column: &'static str,
+    pub old_column: &'static str,
+    pub new_column: &'static str,
+    pub nothing_to_undo: &'static str,
+    pub nothing_to_redo: &'static str,
+    pub change_undone: &'static str,
+    pub change_redone: &'static str,
+    pub undo_last: &'static str,
+    pub redo_last: &'static str,
+    pub negative_hours: &'static str,
+    pub day_over_max: &'static str,
+    pub week_over_max: &'static str,
+    pub weekend_hours: &'static str,
+    pub holiday_hours: &'static str,
+    pub inactive_project: &'static str,
+    pub error_prefix: &'static str,
+    pub warning_prefix: &'static str,
+    pub check_title: &'static str,
+    pub no_violations: &'static str,
+    pub check_limits: &'static str,
+    pub active_column: &'static str,
+    pub toggle_active: &'static str,
+    pub yes: &'static str,
+    pub no: &'static str,
+    pub gaps_title: &'static str,
+    pub no_gaps: &'static str,
+    pub gaps_total: &'static str,
+    pub day_column: &'static str,
+    pub logged_column: &'static str,
+    pub contract_column: &'static str,
+    pub missing_column: &'static str,
+    pub backup_written: &'static str,
+    pub backup_failed: &'static str,
+    pub restore_confirm: &'static str,
+    pub restored: &'static str,
+    pub grid_help: &'static str,
+    pub invalid_hours: &'static str,
+    pub serving: &'static str,
+    pub sync_no_dir: &'static str,
+    pub sync_conflict: &'static str,
+    pub keep_mine: &'static str,
+    pub take_theirs: &'static str,
+    pub enter_other: &'static str,
+    pub sync_applied: &'static str,
+    pub sync_waiting: &'static str,
+    pub sync_nothing_new: &'static str,
+    pub sync_exported: &'static str,
+    pub employees_title: &'static str,
+    pub employee_column: &'static str,
+    pub code_column: &'static str,
+    pub signature_column: &'static str,
+    pub employee_code: &'static str,
+    pub employee_signature: &'static str,
+    pub select_employee: &'static str,
+    pub invalid_employee_code: &'static str,
+    pub employee_exists: &'static str,
+    pub unknown_employee: &'static str,
+    pub team_title: &'static str,
+    pub team_report: &'static str,
+    pub state_approved: &'static str,
+    pub state_rejected: &'static str,
+    pub no_submitted_periods: &'static str,
+    pub approve: &'static str,
+    pub reject: &'static str,
+    pub reviewer_name: &'static str,
+    pub review_comment: &'static str,
+    pub comment_required: &'static str,
+    pub period_approved: &'static str,
+    pub period_rejected: &'static str,
+    pub reviews_title: &'static str,
+    pub reviewer_column: &'static str,
+    pub reviewed_column: &'static str,
+    pub comment_column: &'static str,
+    pub key_generated: &'static str,
+    pub file_signed: &'static str,
+    pub public_key: &'static str,
+    pub signature_invalid: &'static str,
+    pub signature_other_key: &'static str,
+    pub signature_untrusted: &'static str,
+    pub file_altered: &'static str,
+    pub signature_valid: &'static str,
+    pub category_development: &'static str,
+    pub category_meeting: &'static str,
+    pub category_travel: &'static str,
+    pub category_support: &'static str,
+    pub no_category: &'static str,
+    pub other_category: &'static str,
+    pub select_category: &'static str,
+    pub category_name: &'static str,
+    pub category_column: &'static str,
+    pub share_column: &'static str,
+    pub change_category: &'static str,
+    pub category_exists: &'static str,
+    pub categories_report: &'static str,
+    pub category_filter: &'static str,
+    pub billable_column: &'static str,
+    pub non_billable_column: &'static str,
+    pub billable_share_column: &'static str,
+    pub toggle_billable: &'static str,
+    pub set_billable: &'static str,
+    pub select_billable: &'static str,
+    pub billable_default: &'static str,
+    pub billable_follows: &'static str,
+    pub non_billable_mark: &'static str,
+    pub billable_report: &'static str,
+    pub travel_title: &'static str,
+    pub mileage_title: &'static str,
+    pub add_trip: &'static str,
+    pub remove_trip: &'static str,
+    pub select_trip: &'static str,
+    pub trip_date: &'static str,
+    pub trip_from: &'static str,
+    pub trip_to: &'static str,
+    pub trip_km: &'static str,
+    pub trip_mode: &'static str,
+    pub from_column: &'static str,
+    pub to_column: &'static str,
+    pub mode_column: &'static str,
+    pub km_column: &'static str,
+    pub rate_column: &'static str,
+    pub allowance_column: &'static str,
+    pub trips_column: &'static str,
+    pub mode_car: &'static str,
+    pub mode_bike: &'static str,
+    pub mode_public_transport: &'static str,
+    pub mode_other: &'static str,
+    pub no_trips: &'static str,
+    pub invalid_km: &'static str,
+    pub month_locked: &'static str,
+    pub mileage_report: &'static str,
+    pub expenses_title: &'static str,
+    pub receipts_title: &'static str,
+    pub add_expense: &'static str,
+    pub attach_receipt: &'static str,
+    pub remove_expense: &'static str,
+    pub select_expense: &'static str,
+    pub expense_description: &'static str,
+    pub expense_amount: &'static str,
+    pub receipt_path: &'static str,
+    pub description_column: &'static str,
+    pub amount_column: &'static str,
+    pub receipt_column: &'static str,
+    pub check_column: &'static str,
+    pub receipt_ok: &'static str,
+    pub receipt_none: &'static str,
+    pub receipt_missing: &'static str,
+    pub receipt_changed: &'static str,
+    pub no_expenses: &'static str,
+    pub invalid_amount: &'static str,
+    pub invalid_receipt: &'static str,
+    pub receipt_archived: &'static str,
+    pub receipts_incomplete: &'static str,
+    pub receipt_warning: &'static str,
+
+    // Export
+    pub labels: LabelCatalog,
+}
+
+// Default labels of the export, see `layout::Labels`
+pub struct LabelCatalog {
+    pub title: &'static str,
+    pub employee_name: &'static str,
+    pub employee_title: &'static str,
+    pub employee_phone: &'static str,
+    pub client: &'static str,
+    pub role: &'static str,
+    pub project_name: &'static str,
+    pub project_number: &'static str,
+    pub month: &'static str,
+    pub year: &'static str,
+    pub fill_date: &'static str,
+    pub worked_hours: &'static str,
+    pub total: &'static str,
+    pub total_billable: &'static str,
+    pub non_billable: &'static str,
+    pub expenses: &'static str,
+    pub expense_date: &'static str,
+    pub expense_description: &'static str,
+    pub expense_excl_vat: &'static str,
+    pub expense_vat: &'static str,
+    pub expense_incl_vat: &'static str,
+    pub expense_total: &'static str,
+    pub mileage: &'static str,
+    pub mileage_from: &'static str,
+    pub mileage_to: &'static str,
+    pub mileage_mode: &'static str,
+    pub mileage_km: &'static str,
+    pub mileage_rate: &'static str,
+    pub mileage_amount: &'static str,
+    pub sign_client: &'static str,
+    pub sign_employee: &'static str,
+    pub sign_date: &'static str,
+    pub signature_client: &'static str,
+    pub signature_employee: &'static str,
+    pub approved_by: &'static str,
+    pub summary: &'static str,
+    pub project: &'static str,
+    pub week: &'static str,
+    pub description: &'static str,
+}
+
+impl Catalog {
+    pub fn month_name(&self, month: u32) -> &'static str {
+        self.months.get(month.wrapping_sub(1) as usize).copied().unwrap_or("?")
+    }
+
+    pub fn weekday(&self, weekday: Weekday) -> &'static str {
+        self.weekdays[weekday.num_days_from_monday() as usize]
+    }
+
+    pub fn weekday_short(&self, weekday: Weekday) -> &'static str {
+        self.weekdays_short[weekday.num_days_from_monday() as usize]
+    }
+
+    // Replaces the "{}" placeholders in order
+    pub fn fill(template: &str, args: &[&dyn std::fmt::Display]) -> String {
+        let mut out = String::new();
+        let mut parts = template.split("{}");
+        out.push_str(parts.next().unwrap_or_default());
+        for (i, part) in parts.enumerate() {
+            if let Some(arg) = args.get(i) {
+                out.push_str(&arg.to_string());
+            }
+            out.push_str(part);
+        }
+        out
+    }
+
+    pub fn export_labels(&self) -> Labels {
+        let l = &self.labels;
+        Labels {
+            title: l.title.to_string(),
+            employee_name: l.employee_name.to_string(),
+            employee_title: l.employee_title.to_string(),
+            employee_phone: l.employee_phone.to_string(),
+            client: l.client.to_string(),
+            role: l.role.to_string(),
+            project_name: l.project_name.to_string(),
+            project_number: l.project_number.to_string(),
+            month: l.month.to_string(),
+            year: l.year.to_string(),
+            fill_date: l.fill_date.to_string(),
+            worked_hours: l.worked_hours.to_string(),
+            total: l.total.to_string(),
+            total_billable: l.total_billable.to_string(),
+            non_billable: l.non_billable.to_string(),
+            expenses: l.expenses.to_string(),
+            expense_date: l.expense_date.to_string(),
+            expense_description: l.expense_description.to_string(),
+            expense_excl_vat: l.expense_excl_vat.to_string(),
+            expense_vat: l.expense_vat.to_string(),
+            expense_incl_vat: l.expense_incl_vat.to_string(),
+            expense_total: l.expense_total.to_string(),
+            mileage: l.mileage.to_string(),
+            mileage_from: l.mileage_from.to_string(),
+            mileage_to: l.mileage_to.to_string(),
+            mileage_mode: l.mileage_mode.to_string(),
+            mileage_km: l.mileage_km.to_string(),
+            mileage_rate: l.mileage_rate.to_string(),
+            mileage_amount: l.mileage_amount.to_string(),
+            sign_client: l.sign_client.to_string(),
+            sign_employee: l.sign_employee.to_string(),
+            sign_date: l.sign_date.to_string(),
+            signature_client: l.signature_client.to_string(),
+            signature_employee: l.signature_employee.to_string(),
+            approved_by: l.approved_by.to_string(),
+            summary: l.summary.to_string(),
+            project: l.project.to_string(),
+            week: l.week.to_string(),
+            description: l.description.to_string(),
+        }
+    }
+}
+
+pub static NL: Catalog = Catalog {
+    language: "Nederlands",
+    months: [
+        "Januari", "Februari", "Maart", "April", "Mei", "Juni",
+        "Juli", "Augustus", "September", "Oktober", "November", "December",
+    ],
+    weekdays: ["Ma", "Di", "Wo", "Do", "Vr", "Za", "Zo"],
+    weekdays_short: ["Ma", "Di", "Wo", "Do", "Vr", "Za", "Zo"],
+    weekday_initials: ["M", "D", "W", "D", "V", "Z", "Z"],
+
+    template_title: "--- Sjabloonbeheer (Standaard per dag) ---",
+    timesheet_title: "--- Urenstaat: {} ---",
+    month_title: "--- Maandoverzicht (Matrix) ---",
+    projects_title: "--- Projecten ---",
+    project: "Project",
+    total: "TOTAAL",
+    tot: "TOT",
+    language_column: "Taal",
+    action: "Actie:",
+    create_new: "Nieuw",
+    edit_existing: "Bewerken",
+    delete: "Verwijderen",
+    exit: "Afsluiten",
+    project_name: "Projectnaam:",
+    select_project: "Kies project:",
+    are_you_sure: "Weet je het zeker?",
+    enter_hours: "Voer de uren per dag in (Enter om de standaard te behouden):",
+    enter_week: "Week (JJJJ-W##):",
+    no_entries: "Geen uren gevonden voor {}.",
+    load_defaults: "Standaarduren uit de sjablonen laden?",
+    edit_day: "Dag bewerken",
+    add_project: "Project toevoegen",
+    remove_project: "Project verwijderen",
+    select_day: "Kies dag:",
+    hours_for: "Uren voor {}:",
+    remove: "Verwijderen:",
+    year: "Jaar:",
+    month: "Maand (1-12):",
+    no_data: "Geen gegevens gevonden voor {}/{}.",
+    report: "Overzicht: {}/{}",
+    no_projects_export: "Geen projecten gevonden om te exporteren.",
+    select_project_export: "Kies project om te exporteren:",
+    select_projects_week: "Kies projecten voor de weekstaat:",
+    filename: "Bestandsnaam: {}",
+    file_generated: "Bestand aangemaakt: {}",
+    set_language: "Taal instellen",
+    select_language: "Taal voor de export:",
+    default_language: "(standaard)",
+    period_submitted: "{} {}/{} ingediend (SHA-256 {}).",
+    period_locked: "{} {}/{} vergrendeld (SHA-256 {}).",
+    day_locked: "{} op {} is vergrendeld ({}/{}). Ontgrendel de periode eerst met `unlock`.",
+    already_locked: "{} {}/{} is al vergrendeld.",
+    no_locked_periods: "Geen ingediende of vergrendelde periodes.",
+    select_period: "Kies periode:",
+    unlock_reason: "Reden voor ontgrendelen:",
+    period_unlocked: "{} {}/{} ontgrendeld.",
+    periods_title: "--- Ingediende en vergrendelde periodes ---",
+    unlocks_title: "--- Ontgrendelingen ---",
+    state_column: "Status",
+    since_column: "Sinds",
+    period_column: "Periode",
+    unlocked_column: "Ontgrendeld",
+    hours_column: "Uren",
+    reason_column: "Reden",
+    state_submitted: "ingediend",
+    state_locked: "vergrendeld",
+    hours_unchanged: "ongewijzigd",
+    hours_changed: "GEWIJZIGD",
+    history_title: "--- Wijzigingshistorie ---",
+    no_changes: "Geen wijzigingen gevonden.",
+    changed_column: "Gewijzigd",
+    source_column: "Bron",
+    week_column: "Week",
+    date_column: "Datum",
+    old_column: "Oud",
+    new_column: "Nieuw",
+    nothing_to_undo: "Niets om ongedaan te maken.",
+    nothing_to_redo: "Niets om opnieuw uit te voeren.",
+    change_undone: "Wijziging van {} ongedaan gemaakt ({}).",
+    change_redone: "Wijziging van {} opnieuw uitgevoerd ({}).",
+    undo_last: "Laatste wijziging ongedaan maken",
+    redo_last: "Opnieuw uitvoeren",
+    negative_hours: "{} {}: negatief aantal uren ({}).",
+    day_over_max: "{}: {} uur op één dag, meer dan het maximum van {}.",
+    week_over_max: "{}: {} uur in één week, meer dan het maximum van {}.",
+    weekend_hours: "{} {}: uren op {}.",
+    holiday_hours: "{} {}: uren op een feestdag ({}).",
+    inactive_project: "{} {}: project is niet actief.",
+    error_prefix: "Fout",
+    warning_prefix: "Waarschuwing",
+    check_title: "Controle {} – {}",
+    no_violations: "Geen overtredingen gevonden.",
+    check_limits: "Maximum per dag: {} uur, per week: {} uur.",
+    active_column: "Actief",
+    toggle_active: "Actief aan/uit",
+    yes: "ja",
+    no: "nee",
+    gaps_title: "Ontbrekende uren {} – {}",
+    no_gaps: "Geen ontbrekende uren.",
+    gaps_total: "{} werkdagen, {} uur ontbreekt.",
+    day_column: "Dag",
+    logged_column: "Geschreven",
+    contract_column: "Contract",
+    missing_column: "Ontbreekt",
+    backup_written: "Back-up geschreven naar {}.",
+    backup_failed: "Automatische back-up mislukt: {}",
+    restore_confirm: "Alle huidige gegevens vervangen door {}? Eerst wordt een back-up van de huidige gegevens gemaakt.",
+    restored: "{} teruggezet (huidige gegevens bewaard in {}).",
+    grid_help: "Pijltjes: verplaatsen · cijfer/Enter: bewerken · Del: leegmaken · a: project toevoegen · PgUp/PgDn: week · q: stoppen",
+    invalid_hours: "'{}' is geen geldig aantal uren.",
+    serving: "API beschikbaar op http://{} (Ctrl+C om te stoppen).",
+    sync_no_dir: "Stel SYNC_DIR in .env in op de gedeelde map (of gebruik --dir).",
+    sync_conflict: "Conflict bij {} op {}: hier {}, op {} {} (was {}).",
+    keep_mine: "Waarde van dit apparaat houden ({})",
+    take_theirs: "Waarde van {} overnemen ({})",
+    enter_other: "Andere waarde invoeren",
+    sync_applied: "{}: {} wijziging(en) overgenomen, {} al aanwezig, {} conflict(en) opgelost, {} wachtend (vergrendeld).",
+    sync_waiting: "{} wijziging(en) van {} wachten tot hun periode ontgrendeld is; de volgende sync neemt ze dan over.",
+    sync_nothing_new: "Geen nieuwe wijzigingen van andere apparaten.",
+    sync_exported: "{} wijziging(en) geschreven naar {}.",
+    employees_title: "--- Medewerkers ---",
+    employee_column: "Medewerker",
+    code_column: "Code",
+    signature_column: "Handtekening",
+    employee_code: "Code (voor --employee):",
+    employee_signature: "Handtekening (pad naar afbeelding, leeg voor geen):",
+    select_employee: "Kies medewerker:",
+    invalid_employee_code: "'{}' is geen geldige code: begin met een letter en gebruik alleen letters, cijfers, - en _.",
+    employee_exists: "Medewerker '{}' bestaat al.",
+    unknown_employee: "Onbekende medewerker '{}'; voeg deze toe met `employees`.",
+    team_title: "--- Teamoverzicht ---",
+    team_report: "Team: {}/{}",
+    state_approved: "goedgekeurd",
+    state_rejected: "afgekeurd",
+    no_submitted_periods: "Geen ingediende periodes om te beoordelen.",
+    approve: "Goedkeuren",
+    reject: "Afkeuren",
+    reviewer_name: "Naam beoordelaar:",
+    review_comment: "Opmerking:",
+    comment_required: "Geef een opmerking bij het afkeuren.",
+    period_approved: "{} {}/{} goedgekeurd door {}.",
+    period_rejected: "{} {}/{} afgekeurd door {}; de uren kunnen weer worden aangepast.",
+    reviews_title: "--- Beoordelingen ---",
+    reviewer_column: "Beoordelaar",
+    reviewed_column: "Beoordeeld",
+    comment_column: "Opmerking",
+    key_generated: "Nieuwe ondertekeningssleutel aangemaakt: {} (vingerafdruk {})",
+    file_signed: "Ondertekend manifest geschreven: {}",
+    public_key: "Publieke sleutel van {}: {}\nVingerafdruk: {}",
+    signature_invalid: "Ongeldige handtekening: {} is gewijzigd of niet met deze sleutel ondertekend.",
+    signature_other_key: "Het manifest is ondertekend met een andere sleutel (vingerafdruk {}).",
+    signature_untrusted: "Het manifest is ondertekend met een onbekende sleutel die alleen in het manifest zelf staat (vingerafdruk {}). Controleer de vingerafdruk bij de ondertekenaar en geef de publieke sleutel mee met --key.",
+    file_altered: "{} is gewijzigd na ondertekening op {}.",
+    signature_valid: "{} is ongewijzigd sinds {} het ondertekende op {}.\nProject: {}, periode: {}, totaal: {} uur\nVingerafdruk: {}",
+    category_development: "Ontwikkeling",
+    category_meeting: "Overleg",
+    category_travel: "Reistijd",
+    category_support: "Support",
+    no_category: "(geen categorie)",
+    other_category: "Andere categorie...",
+    select_category: "Categorie:",
+    category_name: "Naam van de categorie:",
+    category_column: "Categorie",
+    share_column: "%",
+    change_category: "Categorie wijzigen",
+    category_exists: "{} heeft in {} al een rij met categorie {}.",
+    categories_report: "Per categorie: {}/{}",
+    category_filter: "Alleen categorie {}",
+    billable_column: "Facturabel",
+    non_billable_column: "Niet facturabel",
+    billable_share_column: "% facturabel",
+    toggle_billable: "Facturabel aan/uit",
+    set_billable: "Facturabel instellen",
+    select_billable: "Facturabel:",
+    billable_default: "Volgens project en categorie ({})",
+    billable_follows: "Volgens project en categorie",
+    non_billable_mark: "(niet facturabel)",
+    billable_report: "Facturabel: {}/{}",
+    travel_title: "--- Reizen {}/{} ---",
+    mileage_title: "--- Kilometeroverzicht ---",
+    add_trip: "Rit toevoegen",
+    remove_trip: "Rit verwijderen",
+    select_trip: "Kies rit:",
+    trip_date: "Datum:",
+    trip_from: "Van:",
+    trip_to: "Naar:",
+    trip_km: "Kilometers:",
+    trip_mode: "Vervoer:",
+    from_column: "Van",
+    to_column: "Naar",
+    mode_column: "Vervoer",
+    km_column: "Km",
+    rate_column: "Tarief",
+    allowance_column: "Vergoeding",
+    trips_column: "Ritten",
+    mode_car: "Auto",
+    mode_bike: "Fiets",
+    mode_public_transport: "Openbaar vervoer",
+    mode_other: "Anders",
+    no_trips: "Geen ritten gevonden voor {}/{}.",
+    invalid_km: "Het aantal kilometers moet groter dan 0 zijn.",
+    month_locked: "{} is vergrendeld voor {}/{}. Ontgrendel de periode eerst met `unlock`.",
+    mileage_report: "Per project: {}/{}",
+    expenses_title: "--- Onkosten {}/{} ---",
+    receipts_title: "--- Bonnen {}/{} ---",
+    add_expense: "Onkosten toevoegen",
+    attach_receipt: "Bon bijvoegen",
+    remove_expense: "Onkosten verwijderen",
+    select_expense: "Kies onkosten:",
+    expense_description: "Omschrijving:",
+    expense_amount: "Bedrag incl. BTW:",
+    receipt_path: "Bon (pad naar PDF/JPG/PNG, leeg voor later):",
+    description_column: "Omschrijving",
+    amount_column: "Bedrag",
+    receipt_column: "Bon",
+    check_column: "Controle",
+    receipt_ok: "ok",
+    receipt_none: "geen bon",
+    receipt_missing: "bestand ontbreekt",
+    receipt_changed: "bestand gewijzigd",
+    no_expenses: "Geen onkosten gevonden voor {}/{}.",
+    invalid_amount: "Het bedrag moet groter dan 0 zijn.",
+    invalid_receipt: "Bon {} niet toegevoegd: alleen PDF, JPG en PNG.",
+    receipt_archived: "Bon gearchiveerd als {}",
+    receipts_incomplete: "{} onkosten zonder geldige bon.",
+    receipt_warning: "Let op: {} van {} heeft geen geldige bon ({}).",
+
+    labels: LabelCatalog {
+        title: "TIJDVERANTWOORDINGSFORMULIER",
+        employee_name: "Naam medewerker",
+        employee_title: "Functie in opdracht",
+        employee_phone: "Telefoonnummer",
+        client: "Opdrachtgever",
+        role: "Functie",
+        project_name: "Projectnaam",
+        project_number: "Projectnummer",
+        month: "Maand",
+        year: "Jaar",
+        fill_date: "Invuldatum",
+        worked_hours: "Gewerkte uren",
+        total: "Totaal",
+        total_billable: "Totaal facturabel",
+        non_billable: "Niet facturabel",
+        expenses: "Onkostendeclaratie medewerker (bonnen bijvoegen)",
+        expense_date: "Datum",
+        expense_description: "Omschrijving",
+        expense_excl_vat: "Bedrag excl. BTW",
+        expense_vat: "BTW",
+        expense_incl_vat: "Bedrag incl. BTW",
+        expense_total: "Totaal",
+        mileage: "Kilometerdeclaratie",
+        mileage_from: "Van",
+        mileage_to: "Naar",
+        mileage_mode: "Vervoer",
+        mileage_km: "Km",
+        mileage_rate: "Tarief per km",
+        mileage_amount: "Vergoeding",
+        sign_client: "Opdrachtgever:",
+        sign_employee: "Medewerker:",
+        sign_date: "Datum:",
+        signature_client: "Handtekening opdrachtgever:",
+        signature_employee: "Handtekening medewerker:",
+        approved_by: "Goedgekeurd door {} op {}",
+        summary: "Overzicht",
+        project: "Project",
+        week: "Week",
+        description: "Omschrijving",
+    },
+};
+
+pub static EN: Catalog = Catalog {
+    language: "English",
+    months: [
+        "January", "February", "March", "April", "May", "June",
+        "July", "August", "September", "October", "November", "December",
+    ],
+    weekdays: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    weekdays_short: ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"],
+    weekday_initials: ["M", "T", "W", "T", "F", "S", "S"],
+
+    template_title: "--- Template Management (Daily Defaults) ---",
+    timesheet_title: "--- Timesheet: {} ---",
+    month_title: "--- Monthly Overview (Matrix View) ---",
+    projects_title: "--- Projects ---",
+    project: "Project",
+    total: "TOTAL",
+    tot: "TOT",
+    language_column: "Language",
+    action: "Action:",
+    create_new: "Create New",
+    edit_existing: "Edit Existing",
+    delete: "Delete",
+    exit: "Exit",
+    project_name: "Project Name:",
+    select_project: "Select Project:",
+    are_you_sure: "Are you sure?",
+    enter_hours: "Enter hours for each day (Press Enter to keep default):",
+    enter_week: "Enter Week (YYYY-W##):",
+    no_entries: "No entries found for {}.",
+    load_defaults: "Load defaults from Templates?",
+    edit_day: "Edit Day",
+    add_project: "Add Project",
+    remove_project: "Remove Project",
+    select_day: "Select Day:",
+    hours_for: "Hours for {}:",
+    remove: "Remove:",
+    year: "Year:",
+    month: "Month (1-12):",
+    no_data: "No data found for {}/{}.",
+    report: "Report: {}/{}",
+    no_projects_export: "No projects found in logs to export.",
+    select_project_export: "Select Project to Export:",
+    select_projects_week: "Select Projects for the Week Sheet:",
+    filename: "Filename: {}",
+    file_generated: "File successfully generated: {}",
+    set_language: "Set Language",
+    select_language: "Export language:",
+    default_language: "(default)",
+    period_submitted: "{} {}/{} submitted (SHA-256 {}).",
+    period_locked: "{} {}/{} locked (SHA-256 {}).",
+    day_locked: "{} on {} is locked ({}/{}). Unlock the period first with `unlock`.",
+    already_locked: "{} {}/{} is already locked.",
+    no_locked_periods: "No submitted or locked periods.",
+    select_period: "Select Period:",
+    unlock_reason: "Reason for unlocking:",
+    period_unlocked: "{} {}/{} unlocked.",
+    periods_title: "--- Submitted and Locked Periods ---",
+    unlocks_title: "--- Unlocks ---",
+    state_column: "State",
+    since_column: "Since",
+    period_column: "Period",
+    unlocked_column: "Unlocked",
+    hours_column: "Hours",
+    reason_column: "Reason",
+    state_submitted: "submitted",
+    state_locked: "locked",
+    hours_unchanged: "unchanged",
+    hours_changed: "CHANGED",
+    history_title: "--- Change History ---",
+    no_changes: "No changes found.",
+    changed_column: "Changed",
+    source_column: "Source",
+    week_column: "Week",
+    date_column: "Date",
+    old_column: "Old",
+    new_column: "New",
+    nothing_to_undo: "Nothing to undo.",
+    nothing_to_redo: "Nothing to redo.",
+    change_undone: "Undid the change of {} ({}).",
+    change_redone: "Redid the change of {} ({}).",
+    undo_last: "Undo last change",
+    redo_last: "Redo last change",
+    negative_hours: "{} {}: negative hours ({}).",
+    day_over_max: "{}: {} hours on one day, more than the maximum of {}.",
+    week_over_max: "{}: {} hours in one week, more than the maximum of {}.",
+    weekend_hours: "{} {}: hours on {}.",
+    holiday_hours: "{} {}: hours on a holiday ({}).",
+    inactive_project: "{} {}: project is not active.",
+    error_prefix: "Error",
+    warning_prefix: "Warning",
+    check_title: "Check {} – {}",
+    no_violations: "No violations found.",
+    check_limits: "Maximum per day: {} hours, per week: {} hours.",
+    active_column: "Active",
+    toggle_active: "Toggle Active",
+    yes: "yes",
+    no: "no",
+    gaps_title: "Missing hours {} – {}",
+    no_gaps: "No missing hours.",
+    gaps_total: "{} workdays, {} hours missing.",
+    day_column: "Day",
+    logged_column: "Logged",
+    contract_column: "Contract",
+    missing_column: "Missing",
+    backup_written: "Backup written to {}.",
+    backup_failed: "Automatic backup failed: {}",
+    restore_confirm: "Replace all current data with {}? The current data is backed up first.",
+    restored: "Restored {} (current data saved to {}).",
+    grid_help: "Arrows: move · digit/Enter: edit · Del: clear · a: add project · PgUp/PgDn: week · q: quit",
+    invalid_hours: "'{}' is not a valid number of hours.",
+    serving: "Serving the API on http://{} (Ctrl+C to stop).",
+    sync_no_dir: "Set SYNC_DIR in .env to the shared folder (or use --dir).",
+    sync_conflict: "Conflict for {} on {}: {} here, {} on {} (was {}).",
+    keep_mine: "Keep this device's value ({})",
+    take_theirs: "Take the value of {} ({})",
+    enter_other: "Enter another value",
+    sync_applied: "{}: {} change(s) applied, {} already present, {} conflict(s) resolved, {} waiting (locked).",
+    sync_waiting: "{} change(s) from {} wait until their period is unlocked; the next sync applies them then.",
+    sync_nothing_new: "No new changes from other devices.",
+    sync_exported: "{} change(s) written to {}.",
+    employees_title: "--- Employees ---",
+    employee_column: "Employee",
+    code_column: "Code",
+    signature_column: "Signature",
+    employee_code: "Code (for --employee):",
+    employee_signature: "Signature (path to an image, empty for none):",
+    select_employee: "Select employee:",
+    invalid_employee_code: "'{}' is not a valid code: start with a letter and use only letters, digits, - and _.",
+    employee_exists: "Employee '{}' already exists.",
+    unknown_employee: "Unknown employee '{}'; add them with `employees`.",
+    team_title: "--- Team Overview ---",
+    team_report: "Team: {}/{}",
+    state_approved: "approved",
+    state_rejected: "rejected",
+    no_submitted_periods: "No submitted periods to review.",
+    approve: "Approve",
+    reject: "Reject",
+    reviewer_name: "Reviewer name:",
+    review_comment: "Comment:",
+    comment_required: "A comment is required to reject a period.",
+    period_approved: "{} {}/{} approved by {}.",
+    period_rejected: "{} {}/{} rejected by {}; the hours can be edited again.",
+    reviews_title: "--- Reviews ---",
+    reviewer_column: "Reviewer",
+    reviewed_column: "Reviewed",
+    comment_column: "Comment",
+    key_generated: "New signing key created: {} (fingerprint {})",
+    file_signed: "Signed manifest written: {}",
+    public_key: "Public key of {}: {}\nFingerprint: {}",
+    signature_invalid: "Invalid signature: {} was changed or not signed with this key.",
+    signature_other_key: "The manifest was signed with another key (fingerprint {}).",
+    signature_untrusted: "The manifest was signed with an untrusted key that is only embedded in the manifest itself (fingerprint {}). Check the fingerprint with the signer and pass their public key with --key.",
+    file_altered: "{} was changed after it was signed on {}.",
+    signature_valid: "{} is unchanged since {} signed it on {}.\nProject: {}, period: {}, total: {} hours\nFingerprint: {}",
+    category_development: "Development",
+    category_meeting: "Meeting",
+    category_travel: "Travel",
+    category_support: "Support",
+    no_category: "(no category)",
+    other_category: "Other category...",
+    select_category: "Category:",
+    category_name: "Category name:",
+    category_column: "Category",
+    share_column: "%",
+    change_category: "Change Category",
+    category_exists: "{} already has a row in {} with category {}.",
+    categories_report: "By category: {}/{}",
+    category_filter: "Only category {}",
+    billable_column: "Billable",
+    non_billable_column: "Non-billable",
+    billable_share_column: "% billable",
+    toggle_billable: "Toggle Billable",
+    set_billable: "Set Billable",
+    select_billable: "Billable:",
+    billable_default: "Follow project and category ({})",
+    billable_follows: "Follows project and category",
+    non_billable_mark: "(non-billable)",
+    billable_report: "Billable: {}/{}",
+    travel_title: "--- Trips {}/{} ---",
+    mileage_title: "--- Mileage Report ---",
+    add_trip: "Add Trip",
+    remove_trip: "Remove Trip",
+    select_trip: "Select Trip:",
+    trip_date: "Date:",
+    trip_from: "From:",
+    trip_to: "To:",
+    trip_km: "Kilometers:",
+    trip_mode: "Transport:",
+    from_column: "From",
+    to_column: "To",
+    mode_column: "Transport",
+    km_column: "Km",
+    rate_column: "Rate",
+    allowance_column: "Allowance",
+    trips_column: "Trips",
+    mode_car: "Car",
+    mode_bike: "Bike",
+    mode_public_transport: "Public transport",
+    mode_other: "Other",
+    no_trips: "No trips found for {}/{}.",
+    invalid_km: "Kilometers must be greater than 0.",
+    month_locked: "{} is locked for {}/{}. Unlock the period first with `unlock`.",
+    mileage_report: "Per project: {}/{}",
+    expenses_title: "--- Expenses {}/{} ---",
+    receipts_title: "--- Receipts {}/{} ---",
+    add_expense: "Add Expense",
+    attach_receipt: "Attach Receipt",
+    remove_expense: "Remove Expense",
+    select_expense: "Select Expense:",
+    expense_description: "Description:",
+    expense_amount: "Amount incl. VAT:",
+    receipt_path: "Receipt (path to a PDF/JPG/PNG, empty for later):",
+    description_column: "Description",
+    amount_column: "Amount",
+    receipt_column: "Receipt",
+    check_column: "Check",
+    receipt_ok: "ok",
+    receipt_none: "no receipt",
+    receipt_missing: "file missing",
+    receipt_changed: "file changed",
+    no_expenses: "No expenses found for {}/{}.",
+    invalid_amount: "The amount must be greater than 0.",
+    invalid_receipt: "Receipt {} not attached: only PDF, JPG and PNG.",
+    receipt_archived: "Receipt archived as {}",
+    receipts_incomplete: "{} expenses without a valid receipt.",
+    receipt_warning: "Note: {} of {} has no valid receipt ({}).",
+
+    labels: LabelCatalog {
+        title: "TIMESHEET",
+        employee_name: "Employee name",
+        employee_title: "Role in assignment",
+        employee_phone: "Phone number",
+        client: "Client",
+        role: "Role",
+        project_name: "Project name",
+        project_number: "Project number",
+        month: "Month",
+        year: "Year",
+        fill_date: "Date filled in",
+        worked_hours: "Hours worked",
+        total: "Total",
+        total_billable: "Total billable",
+        non_billable: "Non-billable",
+        expenses: "Employee expense claim (attach receipts)",
+        expense_date: "Date",
+        expense_description: "Description",
+        expense_excl_vat: "Amount excl. VAT",
+        expense_vat: "VAT",
+        expense_incl_vat: "Amount incl. VAT",
+        expense_total: "Total",
+        mileage: "Mileage claim",
+        mileage_from: "From",
+        mileage_to: "To",
+        mileage_mode: "Transport",
+        mileage_km: "Km",
+        mileage_rate: "Rate per km",
+        mileage_amount: "Allowance",
+        sign_client: "Client:",
+        sign_employee: "Employee:",
+        sign_date: "Date:",
+        signature_client: "Client signature:",
+        signature_employee: "Employee signature:",
+        approved_by: "Approved by {} on {}",
+        summary: "Summary",
+        project: "Project",
+        week: "Week",
+        description: "Description",
+    },
+};
+
+pub static DE: Catalog = Catalog {
+    language: "Deutsch",
+    months: [
+        "Januar", "Februar", "März", "April", "Mai", "Juni",
+        "Juli", "August", "September", "Oktober", "November", "Dezember",
+    ],
+    weekdays: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+    weekdays_short: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+    weekday_initials: ["M", "D", "M", "D", "F", "S", "S"],
+
+    template_title: "--- Vorlagenverwaltung (Standard pro Tag) ---",
+    timesheet_title: "--- Stundenzettel: {} ---",
+    month_title: "--- Monatsübersicht (Matrix) ---",
+    projects_title: "--- Projekte ---",
+    project: "Projekt",
+    total: "SUMME",
+    tot: "SUM",
+    language_column: "Sprache",
+    action: "Aktion:",
+    create_new: "Neu anlegen",
+    edit_existing: "Bearbeiten",
+    delete: "Löschen",
+    exit: "Beenden",
+    project_name: "Projektname:",
+    select_project: "Projekt wählen:",
+    are_you_sure: "Sind Sie sicher?",
+    enter_hours: "Stunden pro Tag eingeben (Enter behält den Standardwert):",
+    enter_week: "Woche (JJJJ-W##):",
+    no_entries: "Keine Einträge für {} gefunden.",
+    load_defaults: "Standardwerte aus den Vorlagen laden?",
+    edit_day: "Tag bearbeiten",
+    add_project: "Projekt hinzufügen",
+    remove_project: "Projekt entfernen",
+    select_day: "Tag wählen:",
+    hours_for: "Stunden für {}:",
+    remove: "Entfernen:",
+    year: "Jahr:",
+    month: "Monat (1-12):",
+    no_data: "Keine Daten für {}/{} gefunden.",
+    report: "Bericht: {}/{}",
+    no_projects_export: "Keine Projekte zum Exportieren gefunden.",
+    select_project_export: "Projekt für den Export wählen:",
+    select_projects_week: "Projekte für den Wochenbericht wählen:",
+    filename: "Dateiname: {}",
+    file_generated: "Datei erfolgreich erstellt: {}",
+    set_language: "Sprache festlegen",
+    select_language: "Sprache für den Export:",
+    default_language: "(Standard)",
+    period_submitted: "{} {}/{} eingereicht (SHA-256 {}).",
+    period_locked: "{} {}/{} gesperrt (SHA-256 {}).",
+    day_locked: "{} am {} ist gesperrt ({}/{}). Entsperren Sie den Zeitraum zuerst mit `unlock`.",
+    already_locked: "{} {}/{} ist bereits gesperrt.",
+    no_locked_periods: "Keine eingereichten oder gesperrten Zeiträume.",
+    select_period: "Zeitraum wählen:",
+    unlock_reason: "Grund für das Entsperren:",
+    period_unlocked: "{} {}/{} entsperrt.",
+    periods_title: "--- Eingereichte und gesperrte Zeiträume ---",
+    unlocks_title: "--- Entsperrungen ---",
+    state_column: "Status",
+    since_column: "Seit",
+    period_column: "Zeitraum",
+    unlocked_column: "Entsperrt",
+    hours_column: "Stunden",
+    reason_column: "Grund",
+    state_submitted: "eingereicht",
+    state_locked: "gesperrt",
+    hours_unchanged: "unverändert",
+    hours_changed: "GEÄNDERT",
+    history_title: "--- Änderungsverlauf ---",
+    no_changes: "Keine Änderungen gefunden.",
+    changed_column: "Geändert",
+    source_column: "Quelle",
+    week_column: "Woche",
+    date_column: "Datum",
+    old_column: "Alt",
+    new_column: "Neu",
+    nothing_to_undo: "Nichts rückgängig zu machen.",
+    nothing_to_redo: "Nichts wiederherzustellen.",
+    change_undone: "Änderung vom {} rückgängig gemacht ({}).",
+    change_redone: "Änderung vom {} wiederhergestellt ({}).",
+    undo_last: "Letzte Änderung rückgängig machen",
+    redo_last: "Wiederherstellen",
+    negative_hours: "{} {}: negative Stundenzahl ({}).",
+    day_over_max: "{}: {} Stunden an einem Tag, mehr als das Maximum von {}.",
+    week_over_max: "{}: {} Stunden in einer Woche, mehr als das Maximum von {}.",
+    weekend_hours: "{} {}: Stunden am {}.",
+    holiday_hours: "{} {}: Stunden an einem Feiertag ({}).",
+    inactive_project: "{} {}: Projekt ist nicht aktiv.",
+    error_prefix: "Fehler",
+    warning_prefix: "Warnung",
+    check_title: "Prüfung {} – {}",
+    no_violations: "Keine Verstöße gefunden.",
+    check_limits: "Maximum pro Tag: {} Stunden, pro Woche: {} Stunden.",
+    active_column: "Aktiv",
+    toggle_active: "Aktiv ein/aus",
+    yes: "ja",
+    no: "nein",
+    gaps_title: "Fehlende Stunden {} – {}",
+    no_gaps: "Keine fehlenden Stunden.",
+    gaps_total: "{} Arbeitstage, {} Stunden fehlen.",
+    day_column: "Tag",
+    logged_column: "Erfasst",
+    contract_column: "Vertrag",
+    missing_column: "Fehlt",
+    backup_written: "Sicherung gespeichert unter {}.",
+    backup_failed: "Automatische Sicherung fehlgeschlagen: {}",
+    restore_confirm: "Alle aktuellen Daten durch {} ersetzen? Die aktuellen Daten werden vorher gesichert.",
+    restored: "{} wiederhergestellt (aktuelle Daten gesichert in {}).",
+    grid_help: "Pfeile: bewegen · Ziffer/Enter: bearbeiten · Entf: leeren · a: Projekt hinzufügen · Bild↑/Bild↓: Woche · q: beenden",
+    invalid_hours: "'{}' ist keine gültige Stundenzahl.",
+    serving: "API erreichbar unter http://{} (Strg+C zum Beenden).",
+    sync_no_dir: "SYNC_DIR in .env auf den gemeinsamen Ordner setzen (oder --dir verwenden).",
+    sync_conflict: "Konflikt bei {} am {}: hier {}, auf {} {} (war {}).",
+    keep_mine: "Wert dieses Geräts behalten ({})",
+    take_theirs: "Wert von {} übernehmen ({})",
+    enter_other: "Anderen Wert eingeben",
+    sync_applied: "{}: {} Änderung(en) übernommen, {} bereits vorhanden, {} Konflikt(e) gelöst, {} wartend (gesperrt).",
+    sync_waiting: "{} Änderung(en) von {} warten, bis ihr Zeitraum entsperrt ist; die nächste Synchronisierung übernimmt sie dann.",
+    sync_nothing_new: "Keine neuen Änderungen von anderen Geräten.",
+    sync_exported: "{} Änderung(en) nach {} geschrieben.",
+    employees_title: "--- Mitarbeiter ---",
+    employee_column: "Mitarbeiter",
+    code_column: "Kürzel",
+    signature_column: "Unterschrift",
+    employee_code: "Kürzel (für --employee):",
+    employee_signature: "Unterschrift (Pfad zu einem Bild, leer für keine):",
+    select_employee: "Mitarbeiter wählen:",
+    invalid_employee_code: "'{}' ist kein gültiges Kürzel: mit einem Buchstaben beginnen und nur Buchstaben, Ziffern, - und _ verwenden.",
+    employee_exists: "Mitarbeiter '{}' existiert bereits.",
+    unknown_employee: "Unbekannter Mitarbeiter '{}'; mit `employees` hinzufügen.",
+    team_title: "--- Teamübersicht ---",
+    team_report: "Team: {}/{}",
+    state_approved: "freigegeben",
+    state_rejected: "abgelehnt",
+    no_submitted_periods: "Keine eingereichten Zeiträume zu prüfen.",
+    approve: "Freigeben",
+    reject: "Ablehnen",
+    reviewer_name: "Name Prüfer:",
+    review_comment: "Kommentar:",
+    comment_required: "Zum Ablehnen ist ein Kommentar nötig.",
+    period_approved: "{} {}/{} freigegeben von {}.",
+    period_rejected: "{} {}/{} abgelehnt von {}; die Stunden können wieder bearbeitet werden.",
+    reviews_title: "--- Prüfungen ---",
+    reviewer_column: "Prüfer",
+    reviewed_column: "Geprüft",
+    comment_column: "Kommentar",
+    key_generated: "Neuer Signaturschlüssel erstellt: {} (Fingerabdruck {})",
+    file_signed: "Signiertes Manifest geschrieben: {}",
+    public_key: "Öffentlicher Schlüssel von {}: {}\nFingerabdruck: {}",
+    signature_invalid: "Ungültige Signatur: {} wurde geändert oder nicht mit diesem Schlüssel signiert.",
+    signature_other_key: "Das Manifest wurde mit einem anderen Schlüssel signiert (Fingerabdruck {}).",
+    signature_untrusted: "Das Manifest wurde mit einem nicht vertrauenswürdigen Schlüssel signiert, der nur im Manifest selbst steht (Fingerabdruck {}). Prüfen Sie den Fingerabdruck beim Unterzeichner und geben Sie den öffentlichen Schlüssel mit --key an.",
+    file_altered: "{} wurde nach der Signatur am {} geändert.",
+    signature_valid: "{} ist unverändert, seit {} die Datei am {} signiert hat.\nProjekt: {}, Zeitraum: {}, Summe: {} Stunden\nFingerabdruck: {}",
+    category_development: "Entwicklung",
+    category_meeting: "Besprechung",
+    category_travel: "Reisezeit",
+    category_support: "Support",
+    no_category: "(keine Kategorie)",
+    other_category: "Andere Kategorie...",
+    select_category: "Kategorie:",
+    category_name: "Name der Kategorie:",
+    category_column: "Kategorie",
+    share_column: "%",
+    change_category: "Kategorie ändern",
+    category_exists: "{} hat in {} bereits eine Zeile mit Kategorie {}.",
+    categories_report: "Nach Kategorie: {}/{}",
+    category_filter: "Nur Kategorie {}",
+    billable_column: "Abrechenbar",
+    non_billable_column: "Nicht abrechenbar",
+    billable_share_column: "% abrechenbar",
+    toggle_billable: "Abrechenbar ein/aus",
+    set_billable: "Abrechenbar festlegen",
+    select_billable: "Abrechenbar:",
+    billable_default: "Nach Projekt und Kategorie ({})",
+    billable_follows: "Nach Projekt und Kategorie",
+    non_billable_mark: "(nicht abrechenbar)",
+    billable_report: "Abrechenbar: {}/{}",
+    travel_title: "--- Fahrten {}/{} ---",
+    mileage_title: "--- Kilometerübersicht ---",
+    add_trip: "Fahrt hinzufügen",
+    remove_trip: "Fahrt entfernen",
+    select_trip: "Fahrt wählen:",
+    trip_date: "Datum:",
+    trip_from: "Von:",
+    trip_to: "Nach:",
+    trip_km: "Kilometer:",
+    trip_mode: "Verkehrsmittel:",
+    from_column: "Von",
+    to_column: "Nach",
+    mode_column: "Verkehrsmittel",
+    km_column: "Km",
+    rate_column: "Satz",
+    allowance_column: "Erstattung",
+    trips_column: "Fahrten",
+    mode_car: "Auto",
+    mode_bike: "Fahrrad",
+    mode_public_transport: "Öffentliche Verkehrsmittel",
+    mode_other: "Sonstiges",
+    no_trips: "Keine Fahrten für {}/{} gefunden.",
+    invalid_km: "Die Kilometer müssen größer als 0 sein.",
+    month_locked: "{} ist für {}/{} gesperrt. Entsperren Sie den Zeitraum zuerst mit `unlock`.",
+    mileage_report: "Pro Projekt: {}/{}",
+    expenses_title: "--- Spesen {}/{} ---",
+    receipts_title: "--- Belege {}/{} ---",
+    add_expense: "Spesen hinzufügen",
+    attach_receipt: "Beleg anhängen",
+    remove_expense: "Spesen entfernen",
+    select_expense: "Spesen wählen:",
+    expense_description: "Beschreibung:",
+    expense_amount: "Betrag inkl. MwSt.:",
+    receipt_path: "Beleg (Pfad zu PDF/JPG/PNG, leer für später):",
+    description_column: "Beschreibung",
+    amount_column: "Betrag",
+    receipt_column: "Beleg",
+    check_column: "Prüfung",
+    receipt_ok: "ok",
+    receipt_none: "kein Beleg",
+    receipt_missing: "Datei fehlt",
+    receipt_changed: "Datei geändert",
+    no_expenses: "Keine Spesen für {}/{} gefunden.",
+    invalid_amount: "Der Betrag muss größer als 0 sein.",
+    invalid_receipt: "Beleg {} nicht angehängt: nur PDF, JPG und PNG.",
+    receipt_archived: "Beleg archiviert als {}",
+    receipts_incomplete: "{} Spesen ohne gültigen Beleg.",
+    receipt_warning: "Hinweis: {} vom {} hat keinen gültigen Beleg ({}).",
+
+    labels: LabelCatalog {
+        title: "STUNDENNACHWEIS",
+        employee_name: "Name Mitarbeiter",
+        employee_title: "Funktion im Auftrag",
+        employee_phone: "Telefonnummer",
+        client: "Auftraggeber",
+        role: "Funktion",
+        project_name: "Projektname",
+        project_number: "Projektnummer",
+        month: "Monat",
+        year: "Jahr",
+        fill_date: "Ausfülldatum",
+        worked_hours: "Gearbeitete Stunden",
+        total: "Summe",
+        total_billable: "Summe abrechenbar",
+        non_billable: "Nicht abrechenbar",
+        expenses: "Spesenabrechnung Mitarbeiter (Belege beifügen)",
+        expense_date: "Datum",
+        expense_description: "Beschreibung",
+        expense_excl_vat: "Betrag netto",
+        expense_vat: "MwSt.",
+        expense_incl_vat: "Betrag brutto",
+        expense_total: "Summe",
+        mileage: "Kilometerabrechnung",
+        mileage_from: "Von",
+        mileage_to: "Nach",
+        mileage_mode: "Verkehrsmittel",
+        mileage_km: "Km",
+        mileage_rate: "Satz pro km",
+        mileage_amount: "Erstattung",
+        sign_client: "Auftraggeber:",
+        sign_employee: "Mitarbeiter:",
+        sign_date: "Datum:",
+        signature_client: "Unterschrift Auftraggeber:",
+        signature_employee: "Unterschrift Mitarbeiter:",
+        approved_by: "Freigegeben von {} am {}",
+        summary: "Übersicht",
+        project: "Projekt",
+        week: "Woche",
+        description: "Beschreibung",
+    },
+};
@@ -0,0 +1,431 @@
+use chrono::{Datelike, Local, NaiveDate};
+use inquire::{CustomType, Select, Text};
+use prettytable::{format, Cell, Row, Table};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::{env, error::Error, fs};
+use timesheet_core::{day_records, month_bounds, week_date, Billing, Repository};
+
+use crate::{billing, employees::Employee, locale::Catalog};
+
+// --- Period States ---
+// A project-month without a row is a draft. A submitted, approved or locked project-month
+// refuses edits until it is unlocked with a reason; rejecting a submitted month in `review`
+// turns it back into a draft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockState {
+    Submitted, // sent to the reviewer or client
+    Approved,  // accepted by the reviewer
+    Locked,    // signed, final
+}
+
+impl LockState {
+    fn code(self) -> &'static str {
+        match self { LockState::Submitted => "submitted", LockState::Approved => "approved", LockState::Locked => "locked" }
+    }
+
+    fn parse(code: &str) -> Option<LockState> {
+        match code {
+            "submitted" => Some(LockState::Submitted),
+            "approved" => Some(LockState::Approved),
+            "locked" => Some(LockState::Locked),
+            _ => None,
+        }
+    }
+
+    fn label(self, tr: &Catalog) -> &'static str {
+        match self {
+            LockState::Submitted => tr.state_submitted,
+            LockState::Approved => tr.state_approved,
+            LockState::Locked => tr.state_locked,
+        }
+    }
+}
+
+// Sign-off of an approved month, written into the client's signature block of the export
+#[derive(Debug, Clone)]
+pub struct Approval {
+    pub reviewer: String,
+    pub date: NaiveDate,
+}
+
+struct PeriodLock {
+    project: String,
+    year: i32,
+    month: u32,
+    state: LockState,
+    file: String,
+    file_hash: String,
+    hours_hash: String,
+    since: String,
+}
+
+fn timestamp() -> String {
+    Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+fn file_hash(path: &str) -> Result<String, Box<dyn Error>> {
+    let bytes = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+// Hash of the hours of a project-month, so the periods overview can show they were not changed
+// afterwards. The category and whether the hours are billable are part of it, since both
+// change the export ("Totaal facturabel", the activity lines).
+fn hours_hash(repo: &dyn Repository, billing: &Billing, project: &str, year: i32, month: u32) -> Result<String, Box<dyn Error>> {
+    let (first, last) = month_bounds(year, month)?;
+    let mut records = Vec::new();
+    for entry in repo.entries(None, Some(project))? {
+        let billable = billing.is_billable(&entry);
+        for (day, &hours) in entry.hours.iter().enumerate() {
+            if hours == 0.0 { continue; }
+            let Some(date) = week_date(&entry.week, day) else { continue; };
+            if date < first || date > last { continue; }
+            records.push((date, entry.category.clone(), billable, hours));
+        }
+    }
+    records.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut hasher = Sha256::new();
+    for (date, category, billable, hours) in records {
+        hasher.update(format!("{};{};{};{}\n", date, category, billable, hours));
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+// The hash of periods locked before categories and billable hours were part of it
+fn legacy_hours_hash(repo: &dyn Repository, project: &str, year: i32, month: u32) -> Result<String, Box<dyn Error>> {
+    let (first, last) = month_bounds(year, month)?;
+    let mut hasher = Sha256::new();
+    for record in day_records(repo, Some(first), Some(last), Some(project))? {
+        hasher.update(format!("{};{}\n", record.date, record.hours));
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn load_locks(conn: &Connection) -> Result<Vec<PeriodLock>, Box<dyn Error>> {
+    let mut stmt = conn.prepare(
+        "SELECT project, year, month, state, file, file_hash, hours_hash, since FROM period_locks
+         ORDER BY year, month, project",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?, row.get::<_, i32>(1)?, row.get::<_, u32>(2)?, row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?, row.get::<_, String>(5)?, row.get::<_, String>(6)?, row.get::<_, String>(7)?,
+        ))
+    })?;
+
+    let mut locks = Vec::new();
+    for r in rows {
+        let (project, year, month, state, file, file_hash, hours_hash, since) = r?;
+        let state = LockState::parse(&state).ok_or_else(|| format!("Unknown period state '{}'", state))?;
+        locks.push(PeriodLock { project, year, month, state, file, file_hash, hours_hash, since });
+    }
+    Ok(locks)
+}
+
+pub fn period_state(conn: &Connection, project: &str, year: i32, month: u32) -> Result<Option<LockState>, Box<dyn Error>> {
+    let state: Option<String> = conn
+        .query_row(
+            "SELECT state FROM period_locks WHERE project = ?1 AND year = ?2 AND month = ?3",
+            params![project, year, month],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(state.as_deref().and_then(LockState::parse))
+}
+
+// The sign-off of an approved (or since then locked) month
+pub fn approval(conn: &Connection, project: &str, year: i32, month: u32) -> Result<Option<Approval>, Box<dyn Error>> {
+    let row: Option<(String, String)> = conn
+        .query_row(
+            "SELECT reviewer, reviewed_at FROM period_locks
+             WHERE project = ?1 AND year = ?2 AND month = ?3 AND reviewer IS NOT NULL AND reviewed_at IS NOT NULL",
+            params![project, year, month],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let Some((reviewer, reviewed_at)) = row else { return Ok(None); };
+    let date = NaiveDate::parse_from_str(reviewed_at.get(..10).unwrap_or_default(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid review date '{}'", reviewed_at))?;
+    Ok(Some(Approval { reviewer, date }))
+}
+
+// First of the given days (0 = Monday) of a "YYYY-W##" week that falls in a submitted or locked month
+pub fn locked_day(conn: &Connection, project: &str, week: &str, days: &[usize]) -> Result<Option<NaiveDate>, Box<dyn Error>> {
+    for &day in days {
+        let Some(date) = week_date(week, day) else { continue; };
+        if period_state(conn, project, date.year(), date.month())?.is_some() {
+            return Ok(Some(date));
+        }
+    }
+    Ok(None)
+}
+
+// Refuses a change to trips or expenses in a submitted, approved or locked project-month, like hours
+pub fn refuse_month(conn: &Connection, tr: &Catalog, project: &str, date: NaiveDate) -> Result<bool, Box<dyn Error>> {
+    if period_state(conn, project, date.year(), date.month())?.is_some() {
+        println!("{}", Catalog::fill(tr.month_locked, &[&project, &date.month(), &date.year()]));
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+// Refuses a change to a project's settings (language, billable) while any month with hours of
+// the project is submitted, approved or locked: they change how those months are exported
+pub fn refuse_project(conn: &Connection, repo: &dyn Repository, tr: &Catalog, project: &str) -> Result<bool, Box<dyn Error>> {
+    for entry in repo.entries(None, Some(project))? {
+        let days: Vec<usize> = (0..7).filter(|&d| entry.hours[d] != 0.0).collect();
+        if let Some(date) = locked_day(conn, project, &entry.week, &days)? {
+            println!("{}", Catalog::fill(tr.day_locked, &[&project, &date, &date.month(), &date.year()]));
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Project, year and month
+type ProjectMonth = (String, i32, u32);
+
+fn prompt_period(repo: &dyn Repository, tr: &Catalog) -> Result<Option<ProjectMonth>, Box<dyn Error>> {
+    let mut projects: Vec<String> = repo.entries(None, None)?.into_iter().map(|e| e.project).collect();
+    projects.sort();
+    projects.dedup();
+    if projects.is_empty() {
+        println!("{}", tr.no_projects_export);
+        return Ok(None);
+    }
+    let project = Select::new(tr.select_project, projects).prompt()?;
+
+    let now = Local::now();
+    let (default_year, default_month) = if now.month() == 1 { (now.year() - 1, 12) } else { (now.year(), now.month() - 1) };
+    let year = CustomType::<i32>::new(tr.year).with_default(default_year).prompt()?;
+    let month = CustomType::<u32>::new(tr.month).with_default(default_month).prompt()?;
+    month_bounds(year, month)?;
+
+    Ok(Some((project, year, month)))
+}
+
+// --- Function: Submit / Lock a project-month ---
+// Records the SHA-256 of the exported Urenstaat and of the hours themselves.
+pub fn handle_lock(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, state: LockState, file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let Some((project, year, month)) = prompt_period(repo, tr)? else { return Ok(()); };
+
+    if period_state(conn, &project, year, month)? == Some(LockState::Locked) {
+        println!("{}", Catalog::fill(tr.already_locked, &[&project, &month, &year]));
+        return Ok(());
+    }
+
+    // Defaults to the file written by `export` for this project-month
+    // (after an approval, the export carries the reviewer's sign-off)
+    let file = match file {
+        Some(f) => f.to_string(),
+        None => {
+            let pathname = env::var("PATH_NAME").unwrap_or("".to_string());
+            format!("{}Urenstaat_{}_{}_{}{}.xlsx", pathname, year, month, project, employee.file_suffix())
+        }
+    };
+    let file_hash = file_hash(&file)?;
+    let hours_hash = hours_hash(repo, &billing::load(repo, tr)?, &project, year, month)?;
+
+    conn.execute(
+        "INSERT INTO period_locks (project, year, month, state, file, file_hash, hours_hash, since)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT(project, year, month) DO UPDATE SET
+            state = excluded.state, file = excluded.file, file_hash = excluded.file_hash,
+            hours_hash = excluded.hours_hash, since = excluded.since,
+            reviewer = CASE WHEN excluded.state = 'submitted' THEN NULL ELSE reviewer END,
+            reviewed_at = CASE WHEN excluded.state = 'submitted' THEN NULL ELSE reviewed_at END",
+        params![project, year, month, state.code(), file, file_hash, hours_hash, timestamp()],
+    )?;
+
+    let message = if state == LockState::Submitted { tr.period_submitted } else { tr.period_locked };
+    println!("{}", Catalog::fill(message, &[&project, &month, &year, &file_hash]));
+    Ok(())
+}
+
+// --- Function: Unlock a project-month (reason is logged) ---
+pub fn handle_unlock(conn: &Connection, tr: &Catalog, reason: Option<String>) -> Result<(), Box<dyn Error>> {
+    let locks = load_locks(conn)?;
+    if locks.is_empty() {
+        println!("{}", tr.no_locked_periods);
+        return Ok(());
+    }
+
+    let options: Vec<String> = locks
+        .iter()
+        .map(|l| format!("{} {}/{} ({})", l.project, l.month, l.year, l.state.label(tr)))
+        .collect();
+    let choice = Select::new(tr.select_period, options).raw_prompt()?;
+    let lock = &locks[choice.index];
+
+    let reason = match reason {
+        Some(r) => r,
+        None => Text::new(tr.unlock_reason).prompt()?,
+    };
+    if reason.trim().is_empty() {
+        return Err("A reason is required to unlock a period".into());
+    }
+
+    // The unlock and the log entry go together or not at all
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO period_unlocks (project, year, month, state, file, file_hash, hours_hash, reason, unlocked_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![lock.project, lock.year, lock.month, lock.state.code(), lock.file, lock.file_hash, lock.hours_hash, reason.trim(), timestamp()],
+    )?;
+    tx.execute(
+        "DELETE FROM period_locks WHERE project = ?1 AND year = ?2 AND month = ?3",
+        params![lock.project, lock.year, lock.month],
+    )?;
+    tx.commit()?;
+
+    println!("{}", Catalog::fill(tr.period_unlocked, &[&lock.project, &lock.month, &lock.year]));
+    Ok(())
+}
+
+// --- Function: Review a submitted project-month ---
+// Approving keeps the month closed and records the sign-off; rejecting reopens it for editing.
+// Both decisions are kept in period_reviews with their comment.
+pub fn handle_review(conn: &Connection, tr: &Catalog, reviewer: Option<String>, comment: Option<String>) -> Result<(), Box<dyn Error>> {
+    let submitted: Vec<PeriodLock> = load_locks(conn)?.into_iter().filter(|l| l.state == LockState::Submitted).collect();
+    if submitted.is_empty() {
+        println!("{}", tr.no_submitted_periods);
+        return Ok(());
+    }
+
+    let options: Vec<String> = submitted.iter().map(|l| format!("{} {}/{} ({})", l.project, l.month, l.year, l.since)).collect();
+    let choice = Select::new(tr.select_period, options).raw_prompt()?;
+    let lock = &submitted[choice.index];
+    let approve = Select::new(tr.action, vec![tr.approve, tr.reject]).raw_prompt()?.index == 0;
+
+    // The reviewer is usually the one running the review: EMPLOYEE_NAME from .env
+    let reviewer = match reviewer {
+        Some(r) => r,
+        None => Text::new(tr.reviewer_name).with_default(&Employee::from_env().name).prompt()?,
+    };
+    if reviewer.trim().is_empty() {
+        return Err("A reviewer name is required".into());
+    }
+    let comment = match comment {
+        Some(c) => c,
+        None => Text::new(tr.review_comment).prompt()?,
+    };
+    if !approve && comment.trim().is_empty() {
+        return Err(tr.comment_required.into());
+    }
+
+    let reviewed_at = timestamp();
+    let decision = if approve { LockState::Approved.code() } else { "rejected" };
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO period_reviews (project, year, month, decision, reviewer, comment, reviewed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![lock.project, lock.year, lock.month, decision, reviewer.trim(), comment.trim(), reviewed_at],
+    )?;
+    if approve {
+        tx.execute(
+            "UPDATE period_locks SET state = ?1, reviewer = ?2, reviewed_at = ?3 WHERE project = ?4 AND year = ?5 AND month = ?6",
+            params![LockState::Approved.code(), reviewer.trim(), reviewed_at, lock.project, lock.year, lock.month],
+        )?;
+    } else {
+        tx.execute(
+            "DELETE FROM period_locks WHERE project = ?1 AND year = ?2 AND month = ?3",
+            params![lock.project, lock.year, lock.month],
+        )?;
+    }
+    tx.commit()?;
+
+    let message = if approve { tr.period_approved } else { tr.period_rejected };
+    println!("{}", Catalog::fill(message, &[&lock.project, &lock.month, &lock.year, &reviewer.trim()]));
+    Ok(())
+}
+
+// --- Function: Overview of locked periods and unlocks ---
+pub fn handle_periods(conn: &Connection, repo: &dyn Repository, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+    println!("\n{}", tr.periods_title);
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.project), Cell::new(tr.period_column), Cell::new(tr.state_column), Cell::new(tr.since_column),
+        Cell::new("SHA-256"), Cell::new(tr.hours_column),
+    ]));
+
+    let billing = billing::load(repo, tr)?;
+    for lock in load_locks(conn)? {
+        // Recompute the hours hash to show whether anything changed since the period was locked
+        let unchanged = hours_hash(repo, &billing, &lock.project, lock.year, lock.month)? == lock.hours_hash
+            || legacy_hours_hash(repo, &lock.project, lock.year, lock.month)? == lock.hours_hash;
+        table.add_row(Row::new(vec![
+            Cell::new(&lock.project),
+            Cell::new(&format!("{}/{}", lock.month, lock.year)),
+            Cell::new(lock.state.label(tr)),
+            Cell::new(&lock.since),
+            Cell::new(&lock.file_hash[..12]),
+            if unchanged { Cell::new(tr.hours_unchanged) } else { Cell::new(tr.hours_changed).style_spec("b") },
+        ]));
+    }
+    table.printstd();
+
+    println!("\n{}", tr.unlocks_title);
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.project), Cell::new(tr.period_column), Cell::new(tr.state_column), Cell::new(tr.unlocked_column),
+        Cell::new(tr.reason_column),
+    ]));
+
+    let mut stmt = conn.prepare(
+        "SELECT project, year, month, state, unlocked_at, reason FROM period_unlocks ORDER BY id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?, row.get::<_, i32>(1)?, row.get::<_, u32>(2)?,
+            row.get::<_, String>(3)?, row.get::<_, String>(4)?, row.get::<_, String>(5)?,
+        ))
+    })?;
+    for r in rows {
+        let (project, year, month, state, unlocked_at, reason) = r?;
+        let state = LockState::parse(&state).map(|s| s.label(tr)).unwrap_or("?");
+        table.add_row(Row::new(vec![
+            Cell::new(&project),
+            Cell::new(&format!("{}/{}", month, year)),
+            Cell::new(state),
+            Cell::new(&unlocked_at),
+            Cell::new(&reason),
+        ]));
+    }
+    table.printstd();
+
+    println!("\n{}", tr.reviews_title);
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.project), Cell::new(tr.period_column), Cell::new(tr.state_column), Cell::new(tr.reviewer_column),
+        Cell::new(tr.reviewed_column), Cell::new(tr.comment_column),
+    ]));
+
+    let mut stmt = conn.prepare(
+        "SELECT project, year, month, decision, reviewer, reviewed_at, comment FROM period_reviews ORDER BY id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?, row.get::<_, i32>(1)?, row.get::<_, u32>(2)?, row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?, row.get::<_, String>(5)?, row.get::<_, String>(6)?,
+        ))
+    })?;
+    for r in rows {
+        let (project, year, month, decision, reviewer, reviewed_at, comment) = r?;
+        let decision = if decision == "rejected" { tr.state_rejected } else { tr.state_approved };
+        table.add_row(Row::new(vec![
+            Cell::new(&project),
+            Cell::new(&format!("{}/{}", month, year)),
+            Cell::new(decision),
+            Cell::new(&reviewer),
+            Cell::new(&reviewed_at),
+            Cell::new(&comment),
+        ]));
+    }
+    table.printstd();
+    Ok(())
+}
@@ -1,15 +1,44 @@
 use clap::{Parser, Subcommand};
 use inquire::{Confirm, CustomType, Select, Text};
 use prettytable::{format, Cell, Row, Table};
-use rusqlite::{params, Connection, OptionalExtension};
-use chrono::{Datelike, Local, NaiveDate, Weekday};
+use rusqlite::Connection;
+use chrono::{Datelike, Local, NaiveDate};
 use rust_xlsxwriter::{
-    Color, Format, FormatAlign, FormatBorder, Formula, Image, Workbook, column_number_to_name
+    Color, Format, FormatAlign, FormatBorder, Formula, Image, Workbook, Worksheet, column_number_to_name
 };
 use std::{collections::{BTreeMap, HashMap}, error::Error};
 use dotenv::dotenv;
 use std::env;
 
+mod audit;
+mod backup;
+mod billing;
+mod categories;
+mod dump;
+mod employees;
+mod expenses;
+mod gaps;
+mod layout;
+mod lock;
+mod locale;
+mod pdf;
+mod server;
+mod signing;
+mod sync;
+mod travel;
+mod tui;
+mod undo;
+mod validate;
+mod weekly;
+use layout::{Labels, Layout, Rows};
+use locale::{Catalog, Lang};
+use audit::{ChangeSet, ChangeSource};
+use validate::Rejected;
+use dump::DumpFormat;
+use employees::{Employee, DATABASE};
+use lock::LockState;
+use timesheet_core::{month_bounds, month_categories, month_hours, month_projects, month_totals, Billing, EntryFilter, FileRepository, Repository, SqliteRepository};
+
 // --- CLI Structure ---
 #[derive(Parser)]
 #[command(name = "Timesheet App")]
@@ -17,29 +46,192 @@ use std::env;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Language for terminal output and exports (defaults to TIMESHEET_LANG)
+    #[arg(long, global = true, value_enum)]
+    lang: Option<Lang>,
+    /// Employee (code) whose timesheet to use; defaults to your own
+    #[arg(long, global = true)]
+    employee: Option<String>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     Template,
     Log,
-    Month,
-    Export,
+    /// Full-screen week grid: arrow keys, inline editing, PgUp/PgDn for the previous/next week
+    Grid {
+        /// Week to open (YYYY-W##); defaults to the current week
+        #[arg(long)]
+        week: Option<String>,
+    },
+    /// Hours per project and day for a month, with a breakdown per activity category
+    Month {
+        /// Only hours of this category (code or name, e.g. meeting or Overleg)
+        #[arg(long)]
+        category: Option<String>,
+    },
+    /// Per-project settings, such as the export language
+    Project,
+    /// Employees whose timesheets you keep: name, title, phone and signature
+    Employees,
+    /// Hours of all employees in a month, per employee and project
+    Team,
+    /// Register trips (date, project, from/to, kilometers, transport) for the mileage allowance
+    Travel,
+    /// Trips of a month with kilometers and allowance, per trip and per project
+    Mileage,
+    /// Register expenses and attach their receipts (PDF/JPG/PNG) to the receipt archive
+    Expenses,
+    /// List the expenses of a month with their receipts; exits with 1 when a receipt is missing
+    Receipts,
+    Export {
+        /// Also render the Urenstaat as a PDF next to the xlsx
+        #[arg(long)]
+        pdf: bool,
+        /// Layout definition (TOML) for labels, colors, fonts and blocks; defaults to EXPORT_LAYOUT
+        #[arg(long)]
+        layout: Option<String>,
+        /// Export all projects of the month into one workbook with a summary sheet
+        #[arg(long)]
+        all: bool,
+        /// Export an ISO week (Mon-Sun) with a row per project instead of a month
+        #[arg(long, conflicts_with_all = ["all", "pdf"])]
+        week: bool,
+        /// Write a signed manifest (<file>.sig) with the hash, period and total of each file
+        #[arg(long)]
+        sign: bool,
+        /// Bundle the exported files and the receipts of the month's expenses into a zip
+        #[arg(long, conflicts_with = "week")]
+        zip: bool,
+    },
+    /// Check an exported file against its signed manifest; exits with 1 when it does not match
+    Verify {
+        /// Exported file (xlsx or pdf)
+        file: String,
+        /// Manifest to check against; defaults to <file>.sig
+        #[arg(long)]
+        signature: Option<String>,
+        /// Trusted public key (hex, or a .pub file) that must have signed the manifest
+        #[arg(long)]
+        key: Option<String>,
+    },
+    /// Show the public key that signs your exports (generated on first use)
+    Key,
+    /// Submit a project-month: records the hash of the exported Urenstaat and blocks edits
+    Submit {
+        /// Exported file to record; defaults to the Urenstaat written by `export`
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Lock a signed project-month; locked hours can only be changed after `unlock`
+    Lock {
+        /// Exported file to record; defaults to the Urenstaat written by `export`
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Approve or reject a submitted project-month; a rejection reopens it for editing
+    Review {
+        /// Name of the reviewer; defaults to EMPLOYEE_NAME
+        #[arg(long)]
+        reviewer: Option<String>,
+        /// Comment with the decision (required to reject)
+        #[arg(long)]
+        comment: Option<String>,
+    },
+    /// Unlock a submitted, approved or locked project-month; the reason is logged
+    Unlock {
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// List submitted and locked periods, whether their hours are unchanged, and all unlocks
+    Periods,
+    /// Undo the last change to the hours (persists across sessions)
+    Undo,
+    /// Redo the last undone change
+    Redo,
+    /// Show the change log of the hours, per week and/or project
+    History {
+        /// Only changes in this week (YYYY-W##)
+        #[arg(long)]
+        week: Option<String>,
+        /// Only changes of this project
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Check hours against the validation rules; exits with 1 when a rule is broken
+    Check {
+        /// First date to check (YYYY-MM-DD); defaults to the first day of last month
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last date to check (YYYY-MM-DD); defaults to the last day of last month
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
+    /// List workdays with fewer hours than the contract; exits with 1 when hours are missing
+    Gaps {
+        /// First date to check (YYYY-MM-DD); defaults to the first day of last month
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last date to check (YYYY-MM-DD); defaults to the last day of last month
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
+    /// Back up timesheet.db, or the employee's database (verified with an integrity check)
+    Backup {
+        /// Target file; defaults to BACKUP_DIR/timesheet_<date>_<time>_manual.db
+        #[arg(long)]
+        to: Option<String>,
+    },
+    /// Replace all data with a backup; the current data is backed up first
+    Restore {
+        #[arg(long)]
+        from: String,
+    },
+    /// Serve a local JSON REST API on the same database (templates, weeks, months, exports)
+    Serve {
+        /// Address to listen on; keep it on localhost, the API has no authentication
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: String,
+    },
+    /// Exchange changes with other devices through a shared folder and resolve conflicts
+    Sync {
+        /// Shared folder; defaults to SYNC_DIR from .env
+        #[arg(long)]
+        dir: Option<String>,
+    },
+    /// Dump raw entries (one record per date/project) for scripting and BI tools
+    Dump {
+        /// First date to include (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last date to include (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// Only include this project
+        #[arg(long)]
+        project: Option<String>,
+        /// Only include this activity category
+        #[arg(long)]
+        category: Option<String>,
+        #[arg(long, value_enum, default_value = "csv")]
+        format: DumpFormat,
+    },
 }
-const FONT_NAME: &str = "Verdana";
 
 // --- Data Structs ---
 #[derive(Debug, Clone)]
 struct Template {
-    id: Option<i32>,
     project: String,
+    category: String,
     mon: f64, tue: f64, wed: f64, thu: f64, fri: f64, sat: f64, sun: f64,
 }
 
 #[derive(Debug, Clone)]
 struct Entry {
-    id: Option<i32>,
     project: String,
+    category: String,
+    billable: Option<bool>, // the row's own flag, see billing.rs
+    label: String, // project and category as shown in the menus, marked when not billable
     mon: f64, tue: f64, wed: f64, thu: f64, fri: f64, sat: f64, sun: f64,
 }
 
@@ -49,50 +241,203 @@ impl Template {
     }
 }
 
+// The menus show the day fields; storage uses the core types with an array of 7 days
+impl From<timesheet_core::Template> for Template {
+    fn from(t: timesheet_core::Template) -> Template {
+        let [mon, tue, wed, thu, fri, sat, sun] = t.hours;
+        Template { project: t.project, category: t.category, mon, tue, wed, thu, fri, sat, sun }
+    }
+}
+
 impl Entry {
+    fn new(tr: &Catalog, billing: &Billing, e: timesheet_core::WeekEntry) -> Entry {
+        let mut label = categories::row_label(tr, &e.project, &e.category);
+        if !billing.is_billable(&e) {
+            label = format!("{} {}", label, tr.non_billable_mark);
+        }
+        let [mon, tue, wed, thu, fri, sat, sun] = e.hours;
+        Entry { project: e.project, category: e.category, billable: e.billable, label, mon, tue, wed, thu, fri, sat, sun }
+    }
+
+    fn hours(&self) -> [f64; 7] {
+        [self.mon, self.tue, self.wed, self.thu, self.fri, self.sat, self.sun]
+    }
+
     fn total(&self) -> f64 {
         self.mon + self.tue + self.wed + self.thu + self.fri + self.sat + self.sun
     }
+
+    // Indices (0 = Monday) of the days with hours
+    fn days_with_hours(&self) -> Vec<usize> {
+        [self.mon, self.tue, self.wed, self.thu, self.fri, self.sat, self.sun]
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| **h != 0.0)
+            .map(|(i, _)| i)
+            .collect()
+    }
 }
 
 // Use Box<dyn Error> to handle errors from both Sqlite and Xlsxwriter
 fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok(); // Reads the .env file
-    let conn = Connection::open("timesheet.db")?;
-    init_db(&conn)?;
-
     let cli = Cli::parse();
+    // Terminal output stays English unless a language is chosen
+    let tr = cli.lang.or_else(Lang::from_env).unwrap_or(Lang::En).catalog();
+
+    let mut conn = Connection::open(DATABASE)?;
+    init_db(&conn)?;
+    employees::init_employees(&conn)?;
+    let employee = Employee::select(&conn, tr, cli.employee.as_deref())?;
+    if let Some(path) = employee.database() {
+        conn = Connection::open(path)?;
+        init_db(&conn)?;
+    }
+    let storage = open_storage(&conn, &employee)?;
+    let repo = storage.as_ref();
+    backup::on_startup(&conn, tr);
 
     match cli.command {
-        Commands::Template => handle_templates(&conn)?,
-        Commands::Log => handle_log(&conn)?,
-        Commands::Month => handle_month(&conn)?,
-        // Updated to pass connection
-        Commands::Export => export_timesheet(&conn)?, 
+        Commands::Template => handle_templates(&conn, repo, tr)?,
+        Commands::Log => handle_log(&conn, repo, tr)?,
+        Commands::Grid { week } => tui::handle_grid(&conn, repo, tr, week)?,
+        Commands::Month { category } => match category.map(|name| categories::parse(tr, &name)) {
+            Some(category) => handle_month(&EntryFilter::category(repo, &category), tr, Some(&category))?,
+            None => handle_month(repo, tr, None)?,
+        },
+        Commands::Project => handle_projects(&conn, repo, tr)?,
+        Commands::Employees => employees::handle_employees(&Connection::open(DATABASE)?, tr)?,
+        Commands::Team => employees::handle_team(&Connection::open(DATABASE)?, tr)?,
+        Commands::Travel => travel::handle_travel(&conn, repo, tr)?,
+        Commands::Mileage => travel::handle_mileage(&conn, tr)?,
+        Commands::Expenses => expenses::handle_expenses(&conn, repo, &employee, tr)?,
+        Commands::Receipts => {
+            if expenses::handle_receipts(&conn, &employee, tr)? {
+                std::process::exit(1);
+            }
+        },
+        // Updated to pass the repository
+        Commands::Export { pdf, layout, all, week, sign, zip } => {
+            let opts = ExportOptions { pdf, all, layout, sign, zip };
+            if week { weekly::export_week(repo, &employee, tr, cli.lang, &opts)? } else { export_timesheet(&conn, repo, &employee, tr, cli.lang, opts)? }
+        }
+        Commands::Verify { file, signature, key } => {
+            if !signing::handle_verify(tr, &file, signature, key)? {
+                std::process::exit(1);
+            }
+        },
+        Commands::Key => signing::handle_key(tr, &employee)?,
+        Commands::Submit { file } => lock::handle_lock(&conn, repo, &employee, tr, LockState::Submitted, file.as_deref())?,
+        Commands::Lock { file } => lock::handle_lock(&conn, repo, &employee, tr, LockState::Locked, file.as_deref())?,
+        Commands::Review { reviewer, comment } => lock::handle_review(&conn, tr, reviewer, comment)?,
+        Commands::Unlock { reason } => lock::handle_unlock(&conn, tr, reason)?,
+        Commands::Periods => lock::handle_periods(&conn, repo, tr)?,
+        Commands::Undo => { undo::undo(&conn, repo, tr)?; },
+        Commands::Redo => { undo::redo(&conn, repo, tr)?; },
+        Commands::History { week, project } => audit::handle_history(&conn, tr, week.as_deref(), project.as_deref())?,
+        Commands::Check { from, to } => {
+            let (first, last) = last_month()?;
+            if validate::handle_check(repo, tr, from.unwrap_or(first), to.unwrap_or(last))? {
+                std::process::exit(1);
+            }
+        },
+        Commands::Serve { bind } => server::handle_serve(&conn, repo, &employee, tr, cli.lang, &bind)?,
+        Commands::Sync { dir } => sync::handle_sync(&conn, repo, &employee, tr, dir)?,
+        Commands::Backup { to } => backup::handle_backup(&conn, tr, to)?,
+        Commands::Restore { from } => {
+            drop(storage);
+            // Backups of an older version get the current schema
+            if backup::handle_restore(&mut conn, tr, &from)? {
+                init_db(&conn)?;
+            }
+        },
+        Commands::Gaps { from, to } => {
+            let (first, last) = last_month()?;
+            if gaps::handle_gaps(repo, tr, from.unwrap_or(first), to.unwrap_or(last))? {
+                std::process::exit(1);
+            }
+        },
+        Commands::Dump { from, to, project, category, format } => match category {
+            Some(name) => dump::handle_dump(&EntryFilter::category(repo, &categories::parse(tr, &name)), from, to, project.as_deref(), format)?,
+            None => dump::handle_dump(repo, from, to, project.as_deref(), format)?,
+        },
     }
 
     Ok(())
 }
 
+// --- Storage ---
+// STORAGE in .env: "sqlite" (default) keeps everything in timesheet.db; "files" keeps the
+// templates, hours and project settings as TOML files in STORAGE_DIR (default "timesheet"),
+// e.g. inside a git repository. timesheet.db then only holds the local change log, undo
+// stack and period locks. Employees keep their files in a subfolder named after their code.
+fn open_storage<'c>(conn: &'c Connection, employee: &Employee) -> Result<Box<dyn Repository + 'c>, Box<dyn Error>> {
+    match env::var("STORAGE").unwrap_or_default().trim() {
+        "" | "sqlite" => Ok(Box::new(SqliteRepository::new(conn))),
+        "files" => {
+            let dir = std::path::PathBuf::from(env::var("STORAGE_DIR").unwrap_or("timesheet".to_string()));
+            Ok(Box::new(FileRepository::new(match &employee.code { Some(code) => dir.join(code), None => dir })))
+        }
+        other => Err(format!("Unknown STORAGE '{}', expected sqlite or files", other).into()),
+    }
+}
+
 // --- Database Init ---
 fn init_db(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    // Templates, hours and project settings (see timesheet_core)
+    SqliteRepository::new(conn).init()?;
+    audit::init_changes(conn)?;
+    sync::init_sync(conn)?;
+    travel::init_travel(conn)?;
+    expenses::init_expenses(conn)?;
+    // Submitted/locked project-months and the log of unlocks (see lock.rs)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS period_locks (
+            project TEXT NOT NULL,
+            year INTEGER NOT NULL,
+            month INTEGER NOT NULL,
+            state TEXT NOT NULL,
+            file TEXT NOT NULL,
+            file_hash TEXT NOT NULL,
+            hours_hash TEXT NOT NULL,
+            since TEXT NOT NULL,
+            PRIMARY KEY (project, year, month)
+        )",
+        [],
+    )?;
     conn.execute(
-        "CREATE TABLE IF NOT EXISTS templates (
+        "CREATE TABLE IF NOT EXISTS period_unlocks (
             id INTEGER PRIMARY KEY,
-            project TEXT NOT NULL UNIQUE,
-            mon REAL DEFAULT 0, tue REAL DEFAULT 0, wed REAL DEFAULT 0, 
-            thu REAL DEFAULT 0, fri REAL DEFAULT 0, sat REAL DEFAULT 0, sun REAL DEFAULT 0
+            project TEXT NOT NULL,
+            year INTEGER NOT NULL,
+            month INTEGER NOT NULL,
+            state TEXT NOT NULL,
+            file TEXT NOT NULL,
+            file_hash TEXT NOT NULL,
+            hours_hash TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            unlocked_at TEXT NOT NULL
         )",
         [],
     )?;
+    // Sign-off of approved months, and every approval or rejection with its comment
+    let has_reviewer: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('period_locks') WHERE name = 'reviewer'", [], |row| row.get(0),
+    )?;
+    if !has_reviewer {
+        conn.execute("ALTER TABLE period_locks ADD COLUMN reviewer TEXT", [])?;
+        conn.execute("ALTER TABLE period_locks ADD COLUMN reviewed_at TEXT", [])?;
+    }
     conn.execute(
-        "CREATE TABLE IF NOT EXISTS timesheets (
+        "CREATE TABLE IF NOT EXISTS period_reviews (
             id INTEGER PRIMARY KEY,
-            week TEXT NOT NULL,
             project TEXT NOT NULL,
-            mon REAL DEFAULT 0, tue REAL DEFAULT 0, wed REAL DEFAULT 0, 
-            thu REAL DEFAULT 0, fri REAL DEFAULT 0, sat REAL DEFAULT 0, sun REAL DEFAULT 0,
-            UNIQUE(week, project)
+            year INTEGER NOT NULL,
+            month INTEGER NOT NULL,
+            decision TEXT NOT NULL,
+            reviewer TEXT NOT NULL,
+            comment TEXT NOT NULL,
+            reviewed_at TEXT NOT NULL
         )",
         [],
     )?;
@@ -100,39 +445,30 @@ fn init_db(conn: &Connection) -> Result<(), Box<dyn Error>> {
 }
 
 // --- Function 1: Templates ---
-fn handle_templates(conn: &Connection) -> Result<(), Box<dyn Error>> {
+fn handle_templates(conn: &Connection, repo: &dyn Repository, tr: &Catalog) -> Result<(), Box<dyn Error>> {
     loop {
-        println!("\n--- Template Management (Daily Defaults) ---");
+        println!("\n{}", tr.template_title);
         
-        let mut stmt = conn.prepare("SELECT id, project, mon, tue, wed, thu, fri, sat, sun FROM templates")?;
-        let templates_iter = stmt.query_map([], |row| {
-            Ok(Template {
-                id: Some(row.get(0)?),
-                project: row.get(1)?,
-                mon: row.get(2)?, tue: row.get(3)?, wed: row.get(4)?,
-                thu: row.get(5)?, fri: row.get(6)?, sat: row.get(7)?, sun: row.get(8)?,
-            })
-        })?;
+        let templates_iter = repo.templates()?.into_iter().map(Template::from);
 
         let mut templates: Vec<Template> = Vec::new();
         let mut table = Table::new();
         table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
-        table.set_titles(Row::new(vec![
-            Cell::new("Project"), Cell::new("M"), Cell::new("T"), Cell::new("W"), 
-            Cell::new("T"), Cell::new("F"), Cell::new("S"), Cell::new("S"), Cell::new("TOTAL")
-        ]));
+        let mut titles = vec![Cell::new(tr.project)];
+        titles.extend(tr.weekday_initials.iter().map(|d| Cell::new(d)));
+        titles.push(Cell::new(tr.total));
+        table.set_titles(Row::new(titles));
         
         let (mut sum_m, mut sum_tu, mut sum_w, mut sum_th, mut sum_f, mut sum_sa, mut sum_su, mut week_total) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
         
         for t in templates_iter {
-            let t = t?;
             templates.push(t.clone()); // Store for selection later
             let row_total = t.total();
             
             sum_m += t.mon; sum_tu += t.tue; sum_w += t.wed; sum_th += t.thu; sum_f += t.fri; sum_sa += t.sat; sum_su += t.sun;
             week_total += row_total;
             table.add_row(Row::new(vec![
-                Cell::new(&t.project),
+                Cell::new(&categories::row_label(tr, &t.project, &t.category)),
                 Cell::new(&format_hours(t.mon)), 
                 Cell::new(&format_hours(t.tue)), 
                 Cell::new(&format_hours(t.wed)),
@@ -146,7 +482,7 @@ fn handle_templates(conn: &Connection) -> Result<(), Box<dyn Error>> {
 
         // --- THE DAY TOTAL ROW ---
         table.add_row(Row::new(vec![
-            Cell::new("TOTAL").style_spec("b"),
+            Cell::new(tr.total).style_spec("b"),
             Cell::new(&format_hours(sum_m)).style_spec("b"),
             Cell::new(&format_hours(sum_tu)).style_spec("b"),
             Cell::new(&format_hours(sum_w)).style_spec("b"),
@@ -159,40 +495,37 @@ fn handle_templates(conn: &Connection) -> Result<(), Box<dyn Error>> {
 
         table.printstd();
 
-        let options = vec!["Create New", "Edit Existing", "Delete", "Exit"];
-        let choice = Select::new("Action:", options).prompt();
+        let options = vec![tr.create_new, tr.edit_existing, tr.delete, tr.exit];
+        let choice = Select::new(tr.action, options).prompt();
 
         match choice {
-            Ok("Create New") => {
-                let project = Text::new("Project Name:").prompt().unwrap_or_default();
+            Ok(c) if c == tr.create_new => {
+                let project = Text::new(tr.project_name).prompt().unwrap_or_default();
                 if project.is_empty() { continue; }
+                let Some(category) = categories::choose(repo, tr, "")? else { continue; };
                 
                 // Helper to ask for all days
-                let (m, tu, w, th, f, sa, su) = prompt_week_hours(0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+                let (m, tu, w, th, f, sa, su) = prompt_week_hours(tr, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
 
-                conn.execute(
-                    "INSERT INTO templates (project, mon, tue, wed, thu, fri, sat, sun) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)", 
-                    params![project, m, tu, w, th, f, sa, su]
-                ).map_err(|e| println!("Error: {}", e)).ok();
+                repo.insert_template(&timesheet_core::Template { project, category, hours: [m, tu, w, th, f, sa, su] })
+                    .map_err(|e| println!("Error: {}", e)).ok();
             },
-            Ok("Edit Existing") => {
+            Ok(c) if c == tr.edit_existing => {
                 if templates.is_empty() { continue; }
-                let selection = Select::new("Select Project:", templates.clone()).prompt();
+                let selection = Select::new(tr.select_project, templates.clone()).prompt();
                 if let Ok(tmpl) = selection {
                     // Pre-fill with current values
-                    let (m, tu, w, th, f, sa, su) = prompt_week_hours(tmpl.mon, tmpl.tue, tmpl.wed, tmpl.thu, tmpl.fri, tmpl.sat, tmpl.sun);
+                    let Some(category) = categories::choose(repo, tr, &tmpl.category)? else { continue; };
+                    let (m, tu, w, th, f, sa, su) = prompt_week_hours(tr, tmpl.mon, tmpl.tue, tmpl.wed, tmpl.thu, tmpl.fri, tmpl.sat, tmpl.sun);
                     
-                    conn.execute(
-                        "UPDATE templates SET mon=?1, tue=?2, wed=?3, thu=?4, fri=?5, sat=?6, sun=?7 WHERE id=?8", 
-                        params![m, tu, w, th, f, sa, su, tmpl.id]
-                    )?;
+                    repo.update_template(&timesheet_core::Template { project: tmpl.project, category, hours: [m, tu, w, th, f, sa, su] })?;
                 }
             },
-            Ok("Delete") => {
-                if let Ok(tmpl) = Select::new("Select Project:", templates.clone()).prompt() {
-                     if Confirm::new("Are you sure?").prompt().unwrap_or(false) {
-                        conn.execute("DELETE FROM templates WHERE id = ?1", params![tmpl.id])?;
-                     }
+            Ok(c) if c == tr.delete => {
+                if let Ok(tmpl) = Select::new(tr.select_project, templates.clone()).prompt()
+                    && Confirm::new(tr.are_you_sure).prompt().unwrap_or(false) {
+                    backup::auto(conn, "delete-template")?;
+                    repo.delete_template(&tmpl.project)?;
                 }
             },
             _ => break,
@@ -201,52 +534,69 @@ fn handle_templates(conn: &Connection) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+// Commits a change of the log menu. Warnings are printed; a change that breaks a rule is
+// rolled back and its errors printed, so the menu can continue.
+fn commit_change(tr: &Catalog, changes: ChangeSet, session: &mut Vec<i64>) -> Result<(), Box<dyn Error>> {
+    match changes.commit() {
+        Ok(committed) => {
+            validate::print(tr, &committed.warnings);
+            session.extend(committed.batch);
+            Ok(())
+        }
+        Err(e) => match e.downcast_ref::<Rejected>() {
+            Some(Rejected(errors)) => {
+                validate::print(tr, errors);
+                Ok(())
+            }
+            None => Err(e),
+        },
+    }
+}
+
 // --- Function 2: Timesheets ---
-fn handle_log(conn: &Connection) -> Result<(), Box<dyn Error>> {
+fn handle_log(conn: &Connection, repo: &dyn Repository, tr: &Catalog) -> Result<(), Box<dyn Error>> {
     let current_date = Local::now();
     let default_week = format!("{}-W{:02}", current_date.year(), current_date.iso_week().week()+1);
     
-    let week = Text::new("Enter Week (YYYY-W##):")
+    let week = Text::new(tr.enter_week)
         .with_default(&default_week)
         .prompt()
         .unwrap_or(default_week);
 
+    // Changes made (and undone) in this session, for the undo/redo menu items
+    let mut session: Vec<i64> = Vec::new();
+    let mut session_undone: Vec<i64> = Vec::new();
+
     loop {
         // Load entries
-        let mut stmt = conn.prepare("SELECT id, project, mon, tue, wed, thu, fri, sat, sun FROM timesheets WHERE week = ?1")?;
-        let entries_iter = stmt.query_map(params![week], |row| {
-            Ok(Entry {
-                id: Some(row.get(0)?),
-                project: row.get(1)?,
-                mon: row.get(2)?, tue: row.get(3)?, wed: row.get(4)?,
-                thu: row.get(5)?, fri: row.get(6)?, sat: row.get(7)?, sun: row.get(8)?,
-            })
-        })?;
-
-        let mut entries = Vec::new();
-        for e in entries_iter { entries.push(e?); }
+        let billing = billing::load(repo, tr)?;
+        let entries: Vec<Entry> = repo.entries(Some(&week), None)?.into_iter().map(|e| Entry::new(tr, &billing, e)).collect();
 
         if entries.is_empty() {
-             println!("No entries found for {}.", week);
-             if Confirm::new("Load defaults from Templates?").prompt().unwrap_or(false) {
-                conn.execute(
-                    "INSERT INTO timesheets (week, project, mon, tue, wed, thu, fri, sat, sun) 
-                     SELECT ?1, project, mon, tue, wed, thu, fri, sat, sun FROM templates", 
-                    params![week]
-                )?;
+             println!("{}", Catalog::fill(tr.no_entries, &[&week]));
+             if Confirm::new(tr.load_defaults).prompt().unwrap_or(false) {
+                // Templates for projects with a locked period in this week are skipped
+                let changes = ChangeSet::begin(conn, repo, ChangeSource::Prompt)?;
+                for timesheet_core::Template { project, category, hours } in repo.templates()? {
+                    if let Some(date) = lock::locked_day(conn, &project, &week, &[0, 1, 2, 3, 4, 5, 6])? {
+                        println!("{}", Catalog::fill(tr.day_locked, &[&project, &date, &date.month(), &date.year()]));
+                        continue;
+                    }
+                    changes.insert_row(&week, &project, &category, hours, None)?;
+                }
+                commit_change(tr, changes, &mut session)?;
                 continue; 
              }
         }
 
         // Display Table
-        println!("\n--- Timesheet: {} ---", week);
+        println!("\n{}", Catalog::fill(tr.timesheet_title, &[&week]));
         let mut table = Table::new();
         table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
-        table.set_titles(Row::new(vec![
-            Cell::new("Project"), 
-            Cell::new("Mon"), Cell::new("Tue"), Cell::new("Wed"), Cell::new("Thu"), 
-            Cell::new("Fri"), Cell::new("Sat"), Cell::new("Sun"), Cell::new("TOTAL")
-        ]));
+        let mut titles = vec![Cell::new(tr.project)];
+        titles.extend(tr.weekdays.iter().map(|d| Cell::new(d)));
+        titles.push(Cell::new(tr.total));
+        table.set_titles(Row::new(titles));
 
         let (mut sum_m, mut sum_tu, mut sum_w, mut sum_th, mut sum_f, mut sum_sa, mut sum_su, mut week_total) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
         
@@ -255,7 +605,7 @@ fn handle_log(conn: &Connection) -> Result<(), Box<dyn Error>> {
             sum_m += entry.mon; sum_tu += entry.tue; sum_w += entry.wed; sum_th += entry.thu; sum_f += entry.fri; sum_sa += entry.sat; sum_su += entry.sun;
             week_total += row_total;
             table.add_row(Row::new(vec![
-                Cell::new(&entry.project),
+                Cell::new(&entry.label),
                 Cell::new(&format_hours(entry.mon)), 
                 Cell::new(&format_hours(entry.tue)), 
                 Cell::new(&format_hours(entry.wed)),
@@ -269,7 +619,7 @@ fn handle_log(conn: &Connection) -> Result<(), Box<dyn Error>> {
         
         // --- THE DAY TOTAL ROW ---
         table.add_row(Row::new(vec![
-            Cell::new("TOTAL").style_spec("b"),
+            Cell::new(tr.total).style_spec("b"),
             Cell::new(&format_hours(sum_m)).style_spec("b"),
             Cell::new(&format_hours(sum_tu)).style_spec("b"),
             Cell::new(&format_hours(sum_w)).style_spec("b"),
@@ -283,43 +633,111 @@ fn handle_log(conn: &Connection) -> Result<(), Box<dyn Error>> {
         table.printstd();
 
         // Menu
-        let action = Select::new("Action:", vec!["Edit Day", "Add Project", "Remove Project", "Exit"]).prompt();
+        let mut options = vec![tr.edit_day, tr.add_project, tr.change_category, tr.set_billable, tr.remove_project];
+        if session.last().is_some() && session.last().copied() == undo::last_undoable(conn)? {
+            options.push(tr.undo_last);
+        }
+        if session_undone.last().is_some() && session_undone.last().copied() == undo::last_redoable(conn)? {
+            options.push(tr.redo_last);
+        }
+        options.push(tr.exit);
+        let action = Select::new(tr.action, options).prompt();
 
         match action {
-            Ok("Edit Day") => {
+            Ok(a) if a == tr.edit_day => {
                 if entries.is_empty() { continue; }
                 // 1. Select Project
-                let entry = Select::new("Select Project:", entries.clone()).prompt();
+                let entry = Select::new(tr.select_project, entries.clone()).prompt();
                 if let Ok(e) = entry {
                     // 2. Select Day
-                    let days = vec!["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
-                    let day_choice = Select::new("Select Day:", days).prompt();
+                    let day_choice = Select::new(tr.select_day, tr.weekdays.to_vec()).raw_prompt();
                     
                     if let Ok(d) = day_choice {
-                        let current_val = match d { "Mon"=>e.mon, "Tue"=>e.tue, "Wed"=>e.wed, "Thu"=>e.thu, "Fri"=>e.fri, "Sat"=>e.sat, _=>e.sun };
-                        let new_val = CustomType::<f64>::new(&format!("Hours for {}:", d)).with_default(current_val).prompt().unwrap_or(current_val);
-                        
-                        let col_name = match d { "Mon"=>"mon", "Tue"=>"tue", "Wed"=>"wed", "Thu"=>"thu", "Fri"=>"fri", "Sat"=>"sat", _=>"sun" };
-                        let sql = format!("UPDATE timesheets SET {} = ?1 WHERE id = ?2", col_name);
+                        if let Some(date) = lock::locked_day(conn, &e.project, &week, &[d.index])? {
+                            println!("{}", Catalog::fill(tr.day_locked, &[&e.project, &date, &date.month(), &date.year()]));
+                            continue;
+                        }
+                        let current_val = match d.index { 0=>e.mon, 1=>e.tue, 2=>e.wed, 3=>e.thu, 4=>e.fri, 5=>e.sat, _=>e.sun };
+                        let new_val = CustomType::<f64>::new(&Catalog::fill(tr.hours_for, &[&d.value])).with_default(current_val).prompt().unwrap_or(current_val);
                         
-                        conn.execute(&sql, params![new_val, e.id])?;
+                        let changes = ChangeSet::begin(conn, repo, ChangeSource::Prompt)?;
+                        changes.set_day(&week, &e.project, &e.category, d.index, new_val)?;
+                        commit_change(tr, changes, &mut session)?;
                     }
                 }
             },
-            Ok("Add Project") => {
-                let project = Text::new("Project Name:").prompt().unwrap_or_default();
-                if !project.is_empty() {
-                    conn.execute(
-                        "INSERT INTO timesheets (week, project) VALUES (?1, ?2)", 
-                        params![week, project]
-                    ).ok(); 
+            Ok(a) if a == tr.add_project => {
+                let project = Text::new(tr.project_name).prompt().unwrap_or_default();
+                if project.is_empty() { continue; }
+                let Some(category) = categories::choose(repo, tr, "")? else { continue; };
+                if entries.iter().any(|e| e.project == project && e.category == category) {
+                    println!("{}", Catalog::fill(tr.category_exists, &[&project, &week, &categories::label(tr, &category)]));
+                    continue;
+                }
+                let changes = ChangeSet::begin(conn, repo, ChangeSource::Prompt)?;
+                if changes.insert_row(&week, &project, &category, [0.0; 7], None).is_ok() {
+                    commit_change(tr, changes, &mut session)?;
+                }
+            },
+            Ok(a) if a == tr.change_category => {
+                if entries.is_empty() { continue; }
+                let Ok(entry) = Select::new(tr.select_project, entries.clone()).prompt() else { continue; };
+                let Some(category) = categories::choose(repo, tr, &entry.category)? else { continue; };
+                if category == entry.category { continue; }
+                if entries.iter().any(|e| e.project == entry.project && e.category == category) {
+                    println!("{}", Catalog::fill(tr.category_exists, &[&entry.project, &week, &categories::label(tr, &category)]));
+                    continue;
                 }
+                if let Some(date) = lock::locked_day(conn, &entry.project, &week, &entry.days_with_hours())? {
+                    println!("{}", Catalog::fill(tr.day_locked, &[&entry.project, &date, &date.month(), &date.year()]));
+                    continue;
+                }
+                // The hours and the row's billable flag move to a row with the new category,
+                // as one change for undo
+                let changes = ChangeSet::begin(conn, repo, ChangeSource::Prompt)?;
+                changes.delete_row(&week, &entry.project, &entry.category)?;
+                changes.insert_row(&week, &entry.project, &category, entry.hours(), entry.billable)?;
+                commit_change(tr, changes, &mut session)?;
             },
-            Ok("Remove Project") => {
-                 if let Ok(entry) = Select::new("Remove:", entries).prompt() {
-                     conn.execute("DELETE FROM timesheets WHERE id = ?1", params![entry.id])?;
+            Ok(a) if a == tr.set_billable => {
+                if entries.is_empty() { continue; }
+                let Ok(entry) = Select::new(tr.select_project, entries.clone()).prompt() else { continue; };
+                if let Some(date) = lock::locked_day(conn, &entry.project, &week, &entry.days_with_hours())? {
+                    println!("{}", Catalog::fill(tr.day_locked, &[&entry.project, &date, &date.month(), &date.year()]));
+                    continue;
+                }
+                let default = if billing.default_for(&entry.project, &entry.category) { tr.billable_column } else { tr.non_billable_column };
+                let options = vec![tr.billable_column.to_string(), tr.non_billable_column.to_string(), Catalog::fill(tr.billable_default, &[&default])];
+                let start = match entry.billable { Some(true) => 0, Some(false) => 1, None => 2 };
+                let Ok(choice) = Select::new(tr.select_billable, options).with_starting_cursor(start).raw_prompt() else { continue; };
+                let changes = ChangeSet::begin(conn, repo, ChangeSource::Prompt)?;
+                changes.set_billable(&week, &entry.project, &entry.category, [Some(true), Some(false), None][choice.index])?;
+                commit_change(tr, changes, &mut session)?;
+            },
+            Ok(a) if a == tr.remove_project => {
+                 if let Ok(entry) = Select::new(tr.remove, entries).prompt() {
+                     if let Some(date) = lock::locked_day(conn, &entry.project, &week, &entry.days_with_hours())? {
+                         println!("{}", Catalog::fill(tr.day_locked, &[&entry.project, &date, &date.month(), &date.year()]));
+                         continue;
+                     }
+                     backup::auto(conn, "remove-project")?;
+                     let changes = ChangeSet::begin(conn, repo, ChangeSource::Prompt)?;
+                     changes.delete_row(&week, &entry.project, &entry.category)?;
+                     commit_change(tr, changes, &mut session)?;
                  }
             },
+            Ok(a) if a == tr.undo_last => {
+                if let Some(batch) = undo::undo(conn, repo, tr)? {
+                    session.retain(|b| *b != batch);
+                    session_undone.push(batch);
+                }
+            },
+            Ok(a) if a == tr.redo_last => {
+                if let Some(batch) = undo::redo(conn, repo, tr)? {
+                    session_undone.retain(|b| *b != batch);
+                    session.push(batch);
+                }
+            },
             _ => break,
         }
     }
@@ -327,15 +745,17 @@ fn handle_log(conn: &Connection) -> Result<(), Box<dyn Error>> {
 }
 
 // Helper to prompt for 7 days quickly
-fn prompt_week_hours(m:f64, tu:f64, w:f64, th:f64, f:f64, sa:f64, su:f64) -> (f64, f64, f64, f64, f64, f64, f64) {
-    println!("Enter hours for each day (Press Enter to keep default):");
-    let nm = CustomType::<f64>::new("Mon:").with_default(m).prompt().unwrap_or(m);
-    let ntu = CustomType::<f64>::new("Tue:").with_default(tu).prompt().unwrap_or(tu);
-    let nw = CustomType::<f64>::new("Wed:").with_default(w).prompt().unwrap_or(w);
-    let nth = CustomType::<f64>::new("Thu:").with_default(th).prompt().unwrap_or(th);
-    let nf = CustomType::<f64>::new("Fri:").with_default(f).prompt().unwrap_or(f);
-    let nsa = CustomType::<f64>::new("Sat:").with_default(sa).prompt().unwrap_or(sa);
-    let nsu = CustomType::<f64>::new("Sun:").with_default(su).prompt().unwrap_or(su);
+#[allow(clippy::too_many_arguments)]
+fn prompt_week_hours(tr: &Catalog, m:f64, tu:f64, w:f64, th:f64, f:f64, sa:f64, su:f64) -> (f64, f64, f64, f64, f64, f64, f64) {
+    println!("{}", tr.enter_hours);
+    let day = |i: usize| format!("{}:", tr.weekdays[i]);
+    let nm = CustomType::<f64>::new(&day(0)).with_default(m).prompt().unwrap_or(m);
+    let ntu = CustomType::<f64>::new(&day(1)).with_default(tu).prompt().unwrap_or(tu);
+    let nw = CustomType::<f64>::new(&day(2)).with_default(w).prompt().unwrap_or(w);
+    let nth = CustomType::<f64>::new(&day(3)).with_default(th).prompt().unwrap_or(th);
+    let nf = CustomType::<f64>::new(&day(4)).with_default(f).prompt().unwrap_or(f);
+    let nsa = CustomType::<f64>::new(&day(5)).with_default(sa).prompt().unwrap_or(sa);
+    let nsu = CustomType::<f64>::new(&day(6)).with_default(su).prompt().unwrap_or(su);
     (nm, ntu, nw, nth, nf, nsa, nsu)
 }
 
@@ -347,7 +767,7 @@ impl std::fmt::Display for Template {
 }
 impl std::fmt::Display for Entry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.project)
+        write!(f, "{}", self.label)
     }
 }
 
@@ -359,20 +779,26 @@ fn format_hours(h: f64) -> String {
     }
 }
 
+// Share of a total as a whole percentage, e.g. "75%"
+fn share(hours: f64, total: f64) -> String {
+    if total == 0.0 { String::new() } else { format!("{:.0}%", hours / total * 100.0) }
+}
+
 // --- Function 4: Monthly Overview (Matrix: Projects vs Days) ---
-fn handle_month(conn: &Connection) -> Result<(), Box<dyn Error>> {
+// With a category the repository only holds the hours of that category (see EntryFilter)
+fn handle_month(repo: &dyn Repository, tr: &Catalog, category: Option<&str>) -> Result<(), Box<dyn Error>> {
     // 1. Defaults
     let now = Local::now();
     let default_year = now.year();
     let default_month = if now.month() == 1 { 12 } else { now.month() - 1 };
 
-    println!("\n--- Monthly Overview (Matrix View) ---");
+    println!("\n{}", tr.month_title);
 
     // 2. Input
-    let selected_year = CustomType::<i32>::new("Year:")
+    let selected_year = CustomType::<i32>::new(tr.year)
         .with_default(default_year)
         .prompt()?;
-    let selected_month = CustomType::<u32>::new("Month (1-12):")
+    let selected_month = CustomType::<u32>::new(tr.month)
         .with_default(default_month)
         .prompt()?;
 
@@ -387,51 +813,19 @@ fn handle_month(conn: &Connection) -> Result<(), Box<dyn Error>> {
         .day();
 
     // 4. Fetch & Aggregate Data
-    let mut project_rows: BTreeMap<String, HashMap<u32, f64>> = BTreeMap::new();
     let mut col_totals: HashMap<u32, f64> = HashMap::new();
     let mut grand_total = 0.0;
 
-    let mut stmt = conn.prepare("SELECT week, project, mon, tue, wed, thu, fri, sat, sun FROM timesheets")?;
-    let rows = stmt.query_map([], |row| {
-         Ok((
-            row.get::<_, String>(0)?, // week
-            row.get::<_, String>(1)?, // project
-            [
-                row.get::<_, f64>(2)?, row.get::<_, f64>(3)?, row.get::<_, f64>(4)?, 
-                row.get::<_, f64>(5)?, row.get::<_, f64>(6)?, row.get::<_, f64>(7)?, row.get::<_, f64>(8)?
-            ] 
-        ))
-    })?;
-
-    for r in rows {
-        let (week_str, project, hours) = r?;
-        let parts: Vec<&str> = week_str.split("-W").collect();
-        if parts.len() != 2 { continue; }
-        
-        let w_year: i32 = parts[0].parse().unwrap_or_default();
-        let w_num: u32 = parts[1].parse().unwrap_or_default();
-
-        for (day_idx, &h) in hours.iter().enumerate() {
-            if h == 0.0 { continue; }
-
-            let weekday = match day_idx {
-                0 => Weekday::Mon, 1 => Weekday::Tue, 2 => Weekday::Wed,
-                3 => Weekday::Thu, 4 => Weekday::Fri, 5 => Weekday::Sat, _ => Weekday::Sun
-            };
-
-            if let Some(date) = NaiveDate::from_isoywd_opt(w_year, w_num, weekday) {
-                if date.year() == selected_year && date.month() == selected_month {
-                    let d = date.day();
-                    *project_rows.entry(project.clone()).or_default().entry(d).or_insert(0.0) += h;
-                    *col_totals.entry(d).or_insert(0.0) += h;
-                    grand_total += h;
-                }
-            }
+    let project_rows = month_hours(repo, selected_year, selected_month, None)?;
+    for days_map in project_rows.values() {
+        for (&d, &h) in days_map {
+            *col_totals.entry(d).or_insert(0.0) += h;
+            grand_total += h;
         }
     }
 
     if project_rows.is_empty() {
-        println!("No data found for {}/{}.", selected_month, selected_year);
+        println!("{}", Catalog::fill(tr.no_data, &[&selected_month, &selected_year]));
         return Ok(());
     }
 
@@ -440,12 +834,12 @@ fn handle_month(conn: &Connection) -> Result<(), Box<dyn Error>> {
     table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
 
     // --- Header Row: Project | Mon 01 | Tue 02 | ... | TOT ---
-    let mut header_cells = vec![Cell::new("Project").style_spec("b")];
+    let mut header_cells = vec![Cell::new(tr.project).style_spec("b")];
     
     for d in 1..=days_in_month {
         // Create date object to get the day name
         if let Some(date) = NaiveDate::from_ymd_opt(selected_year, selected_month, d) {
-            let day_name = date.format("%a").to_string(); // e.g., "Mon", "Tue"
+            let day_name = tr.weekday(date.weekday()); // e.g., "Mon", "Tue"
             // Format: Name on top, Number below (e.g., "Mon\n01")
             // Style "bc" = Bold + Center alignment
             header_cells.push(Cell::new(&format!("{}\n{:02}", day_name, d)).style_spec("bc"));
@@ -453,7 +847,7 @@ fn handle_month(conn: &Connection) -> Result<(), Box<dyn Error>> {
              header_cells.push(Cell::new(&format!("{:02}", d)).style_spec("bc"));
         }
     }
-    header_cells.push(Cell::new("TOT").style_spec("b"));
+    header_cells.push(Cell::new(tr.tot).style_spec("b"));
     table.set_titles(Row::new(header_cells));
 
     // --- Project Rows ---
@@ -477,7 +871,7 @@ fn handle_month(conn: &Connection) -> Result<(), Box<dyn Error>> {
     }
 
     // --- Bottom Total Row ---
-    let mut footer_cells = vec![Cell::new("TOTAL").style_spec("b")];
+    let mut footer_cells = vec![Cell::new(tr.total).style_spec("b")];
     for d in 1..=days_in_month {
         let val = col_totals.get(&d).unwrap_or(&0.0);
         if *val > 0.0 {
@@ -489,148 +883,579 @@ fn handle_month(conn: &Connection) -> Result<(), Box<dyn Error>> {
     footer_cells.push(Cell::new(&format_hours(grand_total)).style_spec("bub"));
     table.add_row(Row::new(footer_cells));
 
-    println!("\nReport: {}/{}", selected_month, selected_year);
+    println!("\n{}", Catalog::fill(tr.report, &[&selected_month, &selected_year]));
+    if let Some(category) = category {
+        println!("{}", Catalog::fill(tr.category_filter, &[&categories::label(tr, category)]));
+    }
+    table.printstd();
+
+    if category.is_none() {
+        print_month_categories(repo, tr, selected_year, selected_month)?;
+        print_month_billable(repo, tr, selected_year, selected_month)?;
+    }
+    Ok(())
+}
+
+// Hours per project and category with their share of the project, then per category with
+// their share of the month; skipped when no hours have a category
+fn print_month_categories(repo: &dyn Repository, tr: &Catalog, year: i32, month: u32) -> Result<(), Box<dyn Error>> {
+    let (first, last) = month_bounds(year, month)?;
+    let mut rows: BTreeMap<(String, String), f64> = BTreeMap::new();
+    for record in timesheet_core::day_records(repo, Some(first), Some(last), None)? {
+        *rows.entry((record.project, record.category)).or_insert(0.0) += record.hours;
+    }
+    if rows.keys().all(|(_, category)| category.is_empty()) {
+        return Ok(());
+    }
+
+    let mut project_totals: BTreeMap<&str, f64> = BTreeMap::new();
+    let mut category_totals: BTreeMap<&str, f64> = BTreeMap::new();
+    for ((project, category), hours) in &rows {
+        *project_totals.entry(project).or_insert(0.0) += hours;
+        *category_totals.entry(category).or_insert(0.0) += hours;
+    }
+    let month_total: f64 = project_totals.values().sum();
+
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![Cell::new(tr.project), Cell::new(tr.category_column), Cell::new(tr.tot), Cell::new(tr.share_column)]));
+    for ((project, category), hours) in &rows {
+        table.add_row(Row::new(vec![
+            Cell::new(project),
+            Cell::new(&categories::label(tr, category)),
+            Cell::new(&format_hours(*hours)),
+            Cell::new(&share(*hours, project_totals[project.as_str()])),
+        ]));
+    }
+    for (category, hours) in &category_totals {
+        table.add_row(Row::new(vec![
+            Cell::new(tr.total).style_spec("b"),
+            Cell::new(&categories::label(tr, category)).style_spec("b"),
+            Cell::new(&format_hours(*hours)).style_spec("b"),
+            Cell::new(&share(*hours, month_total)).style_spec("b"),
+        ]));
+    }
+
+    println!("\n{}", Catalog::fill(tr.categories_report, &[&month, &year]));
     table.printstd();
+    Ok(())
+}
 
+// Billable and non-billable hours per project with the billable share; skipped when all
+// hours of the month are billable
+fn print_month_billable(repo: &dyn Repository, tr: &Catalog, year: i32, month: u32) -> Result<(), Box<dyn Error>> {
+    let billing = billing::load(repo, tr)?;
+    let billable = month_totals(&EntryFilter::billable(repo, &billing, true), year, month)?;
+    let non_billable = month_totals(&EntryFilter::billable(repo, &billing, false), year, month)?;
+    if non_billable.is_empty() {
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.project), Cell::new(tr.billable_column), Cell::new(tr.non_billable_column), Cell::new(tr.billable_share_column),
+    ]));
+    let projects: std::collections::BTreeSet<&String> = billable.keys().chain(non_billable.keys()).collect();
+    for project in projects {
+        let yes = billable.get(project).copied().unwrap_or(0.0);
+        let no = non_billable.get(project).copied().unwrap_or(0.0);
+        table.add_row(Row::new(vec![
+            Cell::new(project),
+            Cell::new(&format_hours(yes)),
+            Cell::new(&format_hours(no)),
+            Cell::new(&share(yes, yes + no)),
+        ]));
+    }
+    let (yes, no): (f64, f64) = (billable.values().sum(), non_billable.values().sum());
+    table.add_row(Row::new(vec![
+        Cell::new(tr.total).style_spec("b"),
+        Cell::new(&format_hours(yes)).style_spec("b"),
+        Cell::new(&format_hours(no)).style_spec("b"),
+        Cell::new(&share(yes, yes + no)).style_spec("b"),
+    ]));
+
+    println!("\n{}", Catalog::fill(tr.billable_report, &[&month, &year]));
+    table.printstd();
     Ok(())
 }
 
+// --- Function 5: Project Settings ---
+fn handle_projects(conn: &Connection, repo: &dyn Repository, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+    loop {
+        println!("\n{}", tr.projects_title);
+
+        let mut projects = Vec::new();
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+        table.set_titles(Row::new(vec![
+            Cell::new(tr.project), Cell::new(tr.language_column), Cell::new(tr.active_column), Cell::new(tr.billable_column),
+        ]));
+
+        for timesheet_core::Project { name, lang, active, billable } in repo.projects()? {
+            let lang = lang.as_deref().and_then(Lang::parse);
+            table.add_row(Row::new(vec![
+                Cell::new(&name),
+                Cell::new(&lang.map(|l| l.to_string()).unwrap_or(tr.default_language.to_string())),
+                Cell::new(if active { tr.yes } else { tr.no }),
+                Cell::new(if billable { tr.yes } else { tr.no }),
+            ]));
+            projects.push(name);
+        }
+        table.printstd();
+
+        match Select::new(tr.action, vec![tr.set_language, tr.toggle_active, tr.toggle_billable, tr.exit]).prompt() {
+            Ok(a) if a == tr.set_language => {
+                if projects.is_empty() { continue; }
+                let Ok(project) = Select::new(tr.select_project, projects).prompt() else { continue; };
+                // The language of the exported sheets
+                if lock::refuse_project(conn, repo, tr, &project)? { continue; }
+
+                let mut options = vec![tr.default_language.to_string()];
+                options.extend([Lang::Nl, Lang::En, Lang::De].iter().map(|l| l.to_string()));
+                let Ok(choice) = Select::new(tr.select_language, options).raw_prompt() else { continue; };
+                let lang = [None, Some(Lang::Nl), Some(Lang::En), Some(Lang::De)][choice.index];
+
+                let mut settings = repo.project(&project)?;
+                settings.lang = lang.map(|l| l.code().to_string());
+                repo.save_project(&settings)?;
+            },
+            Ok(a) if a == tr.toggle_active => {
+                if projects.is_empty() { continue; }
+                let Ok(project) = Select::new(tr.select_project, projects).prompt() else { continue; };
+                let mut settings = repo.project(&project)?;
+                settings.active = !settings.active;
+                repo.save_project(&settings)?;
+            },
+            Ok(a) if a == tr.toggle_billable => {
+                if projects.is_empty() { continue; }
+                let Ok(project) = Select::new(tr.select_project, projects).prompt() else { continue; };
+                // The flag changes "Totaal facturabel" of every month, like a row's flag in `log`
+                if lock::refuse_project(conn, repo, tr, &project)? { continue; }
+                let mut settings = repo.project(&project)?;
+                settings.billable = !settings.billable;
+                repo.save_project(&settings)?;
+            },
+            _ => break,
+        }
+    }
+    Ok(())
+}
+
+fn project_lang(repo: &dyn Repository, project: &str) -> Result<Option<Lang>, Box<dyn Error>> {
+    Ok(repo.project(project)?.lang.as_deref().and_then(Lang::parse))
+}
+
 // --- Function 3: Export Timesheet to Excel ---
-fn export_timesheet(conn: &Connection) -> Result<(), Box<dyn Error>> {
+fn export_timesheet(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, lang: Option<Lang>, opts: ExportOptions) -> Result<(), Box<dyn Error>> {
     // 1. Get Distinct Projects for Selection
-    let mut stmt = conn.prepare("SELECT DISTINCT project FROM timesheets ORDER BY project")?;
-    let projects_iter = stmt.query_map([], |row| row.get::<_, String>(0))?;
-    let mut projects = Vec::new();
-    for p in projects_iter { projects.push(p?); }
+    let mut projects: Vec<String> = repo.entries(None, None)?.into_iter().map(|e| e.project).collect();
+    projects.sort();
+    projects.dedup();
 
     if projects.is_empty() {
-        println!("No projects found in logs to export.");
+        println!("{}", tr.no_projects_export);
         return Ok(());
     }
 
-    let selected_project = Select::new("Select Project to Export:", projects).prompt()?;
+    // With --all every project of the month goes into one workbook
+    let selected_project = if opts.all { None } else { Some(Select::new(tr.select_project_export, projects).prompt()?) };
 
     // 2. Select Year and Month
     let now = Local::now();
     let default_year = now.year();
     let default_month = if now.month() == 1 { 12 } else { now.month() - 1 };
 
-    let selected_year = CustomType::<i32>::new("Year:")
+    let selected_year = CustomType::<i32>::new(tr.year)
         .with_default(default_year)
         .prompt()?;
     
-    let selected_month = CustomType::<u32>::new("Month (1-12):")
+    let selected_month = CustomType::<u32>::new(tr.month)
         .with_default(default_month)
         .prompt()?;
 
-    let medewerker_name = env::var("EMPLOYEE_NAME").unwrap_or("John Doe".to_string());
-    let medewerker_title = env::var("EMPLOYEE_TITLE").unwrap_or("Enterprise Architect".to_string());
-    let medewerker_phone = env::var("EMPLOYEE_PHONE").unwrap_or("000000000".to_string());
+    export_month(conn, repo, employee, tr, lang, &opts, selected_project.as_deref(), selected_year, selected_month)?;
+    Ok(())
+}
+
+// Writes the Urenstaat of one project, or of all projects (None) into one workbook; returns the files written
+#[allow(clippy::too_many_arguments)]
+fn export_month(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, lang: Option<Lang>, opts: &ExportOptions, project: Option<&str>, year: i32, month: u32) -> Result<Vec<String>, Box<dyn Error>> {
+    let Some(project) = project else {
+        return export_month_workbook(conn, repo, employee, tr, lang, opts, year, month);
+    };
+
+    let layout = Layout::load(opts.layout.as_deref(), export_lang(repo, lang, Some(project))?)?;
+
+    let urenstaat = load_urenstaat(conn, repo, &billing::load(repo, tr)?, employee, project, year, month)?;
     let pathname = env::var("PATH_NAME").unwrap_or("".to_string());
 
-    let filename = format!("{}Urenstaat_{}_{}_{}.xlsx", pathname, selected_year, selected_month, selected_project);
-    println!("Filename: {}", filename);
-    // 3. Create Workbook
+    let filename = format!("{}Urenstaat_{}_{}_{}{}.xlsx", pathname, year, month, project, employee.file_suffix());
+    println!("{}", Catalog::fill(tr.filename, &[&filename]));
+    write_urenstaat_xlsx(&urenstaat, &layout, &filename)?;
+    println!("{}", Catalog::fill(tr.file_generated, &[&filename]));
+    let mut files = vec![filename];
+
+    if opts.pdf {
+        let pdf_filename = format!("{}Urenstaat_{}_{}_{}{}.pdf", pathname, year, month, project, employee.file_suffix());
+        pdf::write_urenstaat_pdf(&urenstaat, &layout, &pdf_filename)?;
+        println!("{}", Catalog::fill(tr.file_generated, &[&pdf_filename]));
+        files.push(pdf_filename);
+    }
+
+    if opts.sign {
+        let total = urenstaat.total();
+        for file in &files {
+            signing::sign_file(tr, employee, file, &format!("{}-{:02}", year, month), Some(project), total)?;
+        }
+    }
+
+    if opts.zip {
+        let zip_filename = format!("{}Urenstaat_{}_{}_{}{}.zip", pathname, year, month, project, employee.file_suffix());
+        expenses::write_bundle(tr, employee, &zip_filename, &files, &urenstaat.expenses)?;
+        println!("{}", Catalog::fill(tr.file_generated, &[&zip_filename]));
+        files.push(zip_filename);
+    }
+    Ok(files)
+}
+
+// A project with hours, or a new name when there are none yet; None when cancelled
+fn choose_project(repo: &dyn Repository, tr: &Catalog) -> Result<Option<String>, Box<dyn Error>> {
+    let mut projects: Vec<String> = repo.entries(None, None)?.into_iter().map(|e| e.project).collect();
+    projects.sort();
+    projects.dedup();
+    let project = if projects.is_empty() {
+        Text::new(tr.project_name).prompt().unwrap_or_default().trim().to_string()
+    } else {
+        let Ok(project) = Select::new(tr.select_project, projects).prompt() else { return Ok(None); };
+        project
+    };
+    Ok(Some(project).filter(|p| !p.is_empty()))
+}
+
+struct ExportOptions {
+    pdf: bool,
+    all: bool,
+    layout: Option<String>,
+    sign: bool,
+    zip: bool,
+}
+
+// --lang wins over the project's language, then TIMESHEET_LANG; the form itself is Dutch by default
+fn export_lang(repo: &dyn Repository, lang: Option<Lang>, project: Option<&str>) -> Result<Lang, Box<dyn Error>> {
+    if let Some(l) = lang { return Ok(l); }
+    let project_lang = match project {
+        Some(p) => project_lang(repo, p)?,
+        None => None,
+    };
+    Ok(project_lang.or_else(Lang::from_env).unwrap_or(Lang::Nl))
+}
+
+// One workbook for the month: a summary sheet plus one Urenstaat sheet per project
+#[allow(clippy::too_many_arguments)]
+fn export_month_workbook(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, lang: Option<Lang>, opts: &ExportOptions, year: i32, month: u32) -> Result<Vec<String>, Box<dyn Error>> {
+    let projects = month_projects(repo, year, month)?;
+
+    if projects.is_empty() {
+        println!("{}", Catalog::fill(tr.no_data, &[&month, &year]));
+        return Ok(Vec::new());
+    }
+
+    let pathname = env::var("PATH_NAME").unwrap_or("".to_string());
+    let summary_layout = Layout::load(opts.layout.as_deref(), export_lang(repo, lang, None)?)?;
+    let billing = billing::load(repo, tr)?;
+    let mut sheets = Vec::new();
+    for project in &projects {
+        let layout = Layout::load(opts.layout.as_deref(), export_lang(repo, lang, Some(project))?)?;
+        sheets.push((load_urenstaat(conn, repo, &billing, employee, project, year, month)?, layout));
+    }
+
+    let filename = format!("{}Urenstaat_{}_{}{}.xlsx", pathname, year, month, employee.file_suffix());
+    println!("{}", Catalog::fill(tr.filename, &[&filename]));
+
     let mut workbook = Workbook::new();
-    let worksheet = workbook.add_worksheet();
-    
+    let summary = workbook.add_worksheet();
+    let mut used_names = vec![summary_layout.labels.summary.clone()];
+    let mut totals = Vec::new();
+    for (urenstaat, layout) in &sheets {
+        let name = sheet_name(&urenstaat.project, &used_names);
+        // Link to the grand total cell of the project sheet
+        let total_cell = format!("='{}'!AH{}", name.replace('\'', "''"), urenstaat.rows(layout).total + 1);
+        totals.push((urenstaat, total_cell));
+        used_names.push(name);
+    }
+    write_summary_sheet(summary, &totals, &summary_layout)?;
+
+    for ((urenstaat, layout), name) in sheets.iter().zip(used_names.iter().skip(1)) {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(name)?;
+        write_urenstaat_sheet(worksheet, urenstaat, layout)?;
+    }
+    workbook.save(&filename)?;
+    println!("{}", Catalog::fill(tr.file_generated, &[&filename]));
+    let mut files = vec![filename];
+
+    if opts.pdf {
+        for (urenstaat, layout) in &sheets {
+            let pdf_filename = format!("{}Urenstaat_{}_{}_{}{}.pdf", pathname, year, month, urenstaat.project, employee.file_suffix());
+            pdf::write_urenstaat_pdf(urenstaat, layout, &pdf_filename)?;
+            println!("{}", Catalog::fill(tr.file_generated, &[&pdf_filename]));
+            files.push(pdf_filename);
+        }
+    }
+
+    // The workbook covers all projects; each PDF only its own
+    if opts.sign {
+        let period = format!("{}-{:02}", year, month);
+        let total: f64 = sheets.iter().map(|(u, _)| u.total()).sum();
+        signing::sign_file(tr, employee, &files[0], &period, None, total)?;
+        for ((urenstaat, _), file) in sheets.iter().zip(files.iter().skip(1)) {
+            signing::sign_file(tr, employee, file, &period, Some(&urenstaat.project), urenstaat.total())?;
+        }
+    }
+
+    // The receipts of all the month's expenses, also of projects without hours
+    if opts.zip {
+        let zip_filename = format!("{}Urenstaat_{}_{}{}.zip", pathname, year, month, employee.file_suffix());
+        expenses::write_bundle(tr, employee, &zip_filename, &files, &expenses::month_expenses(conn, year, month, None)?)?;
+        println!("{}", Catalog::fill(tr.file_generated, &[&zip_filename]));
+        files.push(zip_filename);
+    }
+    Ok(files)
+}
+
+// Summary sheet: one row per project linking to the total of its sheet
+fn write_summary_sheet(worksheet: &mut Worksheet, totals: &[(&Urenstaat, String)], layout: &Layout) -> Result<(), Box<dyn Error>> {
+    let labels = &layout.labels;
+    let font = layout.font_name.as_str();
+    let font_size = layout.font_size;
+    let Some((first, _)) = totals.first() else { return Ok(()); };
+
+    worksheet.set_name(&labels.summary)?;
     worksheet.protect();
 
-    // Styles
-    let title_fmt = Format::new().set_bold().set_font_size(14).set_align(FormatAlign::Left).set_font_name(FONT_NAME);
-
-    let header_fmt = Format::new().set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10);
-    let header_unlocked_fmt = Format::new().set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10).set_unlocked();
-    let header_address_fmt = Format::new().set_font_name(FONT_NAME).set_font_size(10);
-
-    let sheet_header_fmt = Format::new().set_align(FormatAlign::Center).set_border(FormatBorder::Thin).set_background_color(Color::RGB(0xF28E00)).set_font_name(FONT_NAME).set_font_size(10);
-    let sheet_description_fmt = Format::new().set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10);
-    let sheet_hours_fmt = Format::new().set_align(FormatAlign::Center).set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10);
-    let sheet_description_unlocked_fmt = Format::new().set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10).set_unlocked();
-    let sheet_hours_unlocked_fmt = Format::new().set_align(FormatAlign::Center).set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10).set_unlocked();
-    let sheet_total_description_fmt = Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Left).set_font_name(FONT_NAME).set_font_size(10);
-    let sheet_rowtotal_fmt = Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Center).set_font_name(FONT_NAME).set_font_size(10);
-    let sheet_daytotal_fmt = Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Center).set_font_name(FONT_NAME).set_font_size(10);
-    
-    let header_expenses_fmt = Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Left).set_font_name(FONT_NAME).set_font_size(10);
-    let header_expenses_total_fmt = Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Right).set_font_name(FONT_NAME).set_font_size(10);
-    let expenses_date_fmt = Format::new().set_border(FormatBorder::Thin).set_num_format("dd-mm-yyyy").set_font_name(FONT_NAME).set_font_size(10).set_unlocked();
-    let expenses_description_fmt = Format::new().set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10).set_unlocked();
-    let expenses_amount_fmt = Format::new().set_num_format("€ #,##0.00").set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10);
-    let expenses_amount_unlocked_fmt = Format::new().set_num_format("€ #,##0.00").set_border(FormatBorder::Thin).set_font_name(FONT_NAME).set_font_size(10).set_unlocked();
-    let expenses_total_description_fmt = Format::new().set_font_name(FONT_NAME).set_font_size(10);
-      
-    let footer_header_fmt = Format::new().set_bold().set_align(FormatAlign::Left).set_font_name(FONT_NAME).set_font_size(10);
-    let footer_fmt = Format::new().set_align(FormatAlign::Left).set_font_name(FONT_NAME).set_font_size(10).set_unlocked();
-    let footer_date_fmt = Format::new().set_num_format("dd-mm-yyyy").set_font_name(FONT_NAME).set_font_size(10).set_unlocked();
-    let footer_signature_fmt = Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Top).set_font_name(FONT_NAME).set_font_size(10);
-    
-    // Layout
-    worksheet.set_landscape();
+    let title_fmt = Format::new().set_bold().set_font_size(layout.title_font_size).set_align(FormatAlign::Left).set_font_name(font);
+    let header_fmt = Format::new().set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size);
+    let column_fmt = Format::new().set_align(FormatAlign::Center).set_border(FormatBorder::Thin).set_background_color(Color::RGB(layout.header_rgb()?)).set_font_name(font).set_font_size(font_size);
+    let project_fmt = Format::new().set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size);
+    let hours_fmt = Format::new().set_align(FormatAlign::Center).set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size);
+    let total_description_fmt = Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Left).set_font_name(font).set_font_size(font_size);
+    let total_fmt = Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Center).set_font_name(font).set_font_size(font_size);
 
-    worksheet.set_print_area(0, 0, 45, 33)?;
-    worksheet.set_print_gridlines(false);
-    worksheet.set_paper_size(9); // A4
-    worksheet.set_margins(0.25, 0.25, 0.5, 0.5, 0.25, 0.25);
-    worksheet.set_print_fit_to_pages(1,1);
+    worksheet.set_column_width(0, 6)?;
+    worksheet.set_column_width(1, 30)?;
+    worksheet.set_column_width(2, 12)?;
 
-    for col in 0..=32 { worksheet.set_column_width(col, 6)?; }
-    worksheet.set_column_width(1, 20)?; 
-    worksheet.set_column_width(33, 10)?;
+    worksheet.write_string_with_format(1, 1, &labels.summary, &title_fmt)?;
+    worksheet.write_string_with_format(3, 1, &labels.month, &header_fmt)?;
+    worksheet.write_string_with_format(3, 2, layout.lang.catalog().month_name(first.month), &header_fmt)?;
+    worksheet.write_string_with_format(4, 1, &labels.year, &header_fmt)?;
+    worksheet.write_number_with_format(4, 2, first.year, &header_fmt)?;
 
-    // --- Write Headers ---
-    worksheet.write_string_with_format(1, 1, "TIJDVERANTWOORDINGSFORMULIER", &title_fmt)?;
-
-    worksheet.write_string_with_format(3, 1, "Naam medewerker", &header_fmt)?;
-    worksheet.merge_range(3, 2, 3, 9, &medewerker_name,&header_fmt)?;
-
-    worksheet.write_string_with_format(4, 1, "Functie in opdracht", &header_fmt)?;
-    worksheet.merge_range(4, 2, 4, 9, &medewerker_title,&header_unlocked_fmt)?;
-    worksheet.write_string_with_format(5, 1, "Telefoonnummer", &header_fmt)?;
-    worksheet.merge_range(5, 2, 5, 9, &medewerker_phone,&header_unlocked_fmt)?;
-
-    worksheet.write_string_with_format(7, 1, "Opdrachtgever", &header_fmt)?;
-    worksheet.merge_range(7, 2, 7, 9, &selected_project,&header_unlocked_fmt)?;
-    worksheet.write_string_with_format(8, 1, "Functie", &header_fmt)?;
-    worksheet.merge_range(8, 2, 8, 9, "",&header_unlocked_fmt)?;
-    worksheet.write_string_with_format(9, 1, "Projectnaam", &header_fmt)?;
-    worksheet.merge_range(9, 2, 9, 9, "",&header_unlocked_fmt)?;
-    worksheet.write_string_with_format(10, 1, "Projectnummer", &header_fmt)?;
-    worksheet.merge_range(10, 2, 10, 9, "",&header_unlocked_fmt)?;
- 
-    let month_name_str = month_name(selected_month);
- 
-    worksheet.merge_range(3, 12, 3, 15, "Maand",&header_fmt)?;
-    worksheet.merge_range(4, 12, 4, 15, "Jaar",&header_fmt)?;
-    worksheet.merge_range(5, 12, 5, 15, "Invuldatum",&header_fmt)?;
-    
-    worksheet.merge_range(3, 16, 3, 20, month_name_str,&header_fmt)?;
-    worksheet.merge_range(4, 16, 4, 20, &selected_year.to_string(),&header_fmt)?;
-    worksheet.merge_range(5, 16, 5, 20, &now.format("%d-%m-%Y").to_string(),&header_unlocked_fmt)?;
+    let first_row = 7;
+    worksheet.write_string_with_format(first_row - 1, 1, &labels.project, &column_fmt)?;
+    worksheet.write_string_with_format(first_row - 1, 2, &labels.total, &column_fmt)?;
 
-    let logo_image = Image::new("logo.jpg")?;
-    let logo_image = logo_image.set_scale_to_size(300, 200, true);
+    let mut grand_total = 0.0;
+    let mut non_billable = 0.0;
+    for (i, (urenstaat, total_cell)) in totals.iter().enumerate() {
+        let row = first_row + i as u32;
+        let total = urenstaat.billable();
+        grand_total += total;
+        non_billable += urenstaat.non_billable.values().sum::<f64>();
+        worksheet.write_string_with_format(row, 1, &urenstaat.project, &project_fmt)?;
+        worksheet.write_formula_with_format(row, 2, Formula::new(total_cell).set_result(total.to_string()), &hours_fmt)?;
+    }
 
-    worksheet.insert_image(2, 23, &logo_image)?;
+    let total_row = first_row + totals.len() as u32;
+    let formula = format!("=SUM(C{}:C{})", first_row + 1, total_row);
+    worksheet.write_string_with_format(total_row, 1, &labels.total_billable, &total_description_fmt)?;
+    worksheet.write_formula_with_format(total_row, 2, Formula::new(formula).set_result(grand_total.to_string()), &total_fmt)?;
+    if non_billable != 0.0 {
+        worksheet.write_string_with_format(total_row + 1, 1, &labels.non_billable, &project_fmt)?;
+        worksheet.write_number_with_format(total_row + 1, 2, non_billable, &hours_fmt)?;
+    }
+    Ok(())
+}
 
-    worksheet.write_string_with_format(7, 23, "Hoeksekade 160", &header_address_fmt)?;
-    worksheet.write_string_with_format(8, 23, "2661, JL Bergschenhoek", &header_address_fmt)?;
+// Excel sheet names: max 31 characters, none of []:*?/\ and unique within the workbook
+fn sheet_name(project: &str, used: &[String]) -> String {
+    let clean: String = project
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+        .collect();
+    let clean = clean.trim_matches('\'');
+    let base: String = if clean.is_empty() { "Project".to_string() } else { clean.chars().take(31).collect() };
+
+    let mut name = base.clone();
+    let mut n = 2;
+    while used.iter().any(|u| u.to_lowercase() == name.to_lowercase()) {
+        let suffix = format!(" ({})", n);
+        name = format!("{}{}", base.chars().take(31 - suffix.len()).collect::<String>(), suffix);
+        n += 1;
+    }
+    name
+}
 
+// Default period of `check` and `gaps`
+fn last_month() -> Result<(NaiveDate, NaiveDate), Box<dyn Error>> {
+    let now = Local::now();
+    let (year, month) = if now.month() == 1 { (now.year() - 1, 12) } else { (now.year(), now.month() - 1) };
+    month_bounds(year, month)
+}
 
-    // --- Calendar Grid & Data Fetching ---
-    let start_row_cal = 14; 
-    let start_row_hours = 16;
-    
-    // Dutch short days
-    let days_map = |w: Weekday| match w {
-        Weekday::Mon => "Ma", Weekday::Tue => "Di", Weekday::Wed => "Wo",
-        Weekday::Thu => "Do", Weekday::Fri => "Vr", Weekday::Sat => "Za", Weekday::Sun => "Zo"
-    };
+// Everything needed to render one Urenstaat, shared by the Excel and PDF renderers
+struct Urenstaat {
+    project: String,
+    year: i32,
+    month: u32,
+    employee: Employee,
+    fill_date: NaiveDate,
+    approval: Option<lock::Approval>,
+    activities: BTreeMap<String, BTreeMap<u32, f64>>, // category ("" uncategorised) -> day of month -> billable hours
+    non_billable: BTreeMap<u32, f64>, // day of month -> hours outside "Totaal facturabel"
+    expenses: Vec<expenses::Expense>, // of the project in the month, for the expense rows
+    trips: Vec<travel::Trip>, // of the project in the month, for the mileage block
+}
+
+// Hour lines of the monthly sheet; the ones without an activity are left blank to fill in by hand
+const MIN_LINES: usize = 5;
+
+impl Urenstaat {
+    // All hours of the sheet, billable or not
+    fn total(&self) -> f64 {
+        self.billable() + self.non_billable.values().sum::<f64>()
+    }
+
+    fn billable(&self) -> f64 {
+        self.activities.values().flat_map(|days| days.values()).sum()
+    }
+
+    // A line per activity, growing beyond the standard grid when needed, per expense and per trip
+    fn rows(&self, layout: &Layout) -> Rows {
+        layout.rows_with_lines(self.activities.len().max(MIN_LINES) as u32, self.expenses.len() as u32, self.trips.len() as u32)
+    }
+}
+
+fn load_urenstaat(conn: &Connection, repo: &dyn Repository, billing: &Billing, employee: &Employee, project: &str, year: i32, month: u32) -> Result<Urenstaat, Box<dyn Error>> {
+    let activities = month_categories(&EntryFilter::billable(repo, billing, true), year, month, Some(project))?;
+    let non_billable = month_hours(&EntryFilter::billable(repo, billing, false), year, month, Some(project))?.remove(project).unwrap_or_default();
+
+    Ok(Urenstaat {
+        project: project.to_string(),
+        year,
+        month,
+        employee: employee.clone(),
+        fill_date: Local::now().date_naive(),
+        approval: lock::approval(conn, project, year, month)?,
+        activities,
+        non_billable,
+        expenses: expenses::month_expenses(conn, year, month, Some(project))?,
+        trips: travel::month_trips(conn, year, month, Some(project))?,
+    })
+}
+
+// Cell formats of the Urenstaat, shared by the monthly and weekly sheets
+struct SheetFormats {
+    title: Format,
+    header: Format,
+    header_unlocked: Format,
+    header_address: Format,
+    sheet_header: Format,
+    sheet_description: Format,
+    sheet_hours: Format,
+    sheet_description_unlocked: Format,
+    sheet_hours_unlocked: Format,
+    sheet_total_description: Format,
+    sheet_rowtotal: Format,
+    sheet_daytotal: Format,
+    header_expenses: Format,
+    header_expenses_total: Format,
+    expenses_date: Format,
+    expenses_description: Format,
+    expenses_amount: Format,
+    expenses_amount_unlocked: Format,
+    expenses_total_description: Format,
+    mileage_km: Format,
+    footer_header: Format,
+    footer: Format,
+    footer_date: Format,
+    footer_signature: Format,
+}
+
+impl SheetFormats {
+    fn new(layout: &Layout) -> Result<SheetFormats, Box<dyn Error>> {
+        let font = layout.font_name.as_str();
+        let font_size = layout.font_size;
+        Ok(SheetFormats {
+            title: Format::new().set_bold().set_font_size(layout.title_font_size).set_align(FormatAlign::Left).set_font_name(font),
+            header: Format::new().set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size),
+            header_unlocked: Format::new().set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size).set_unlocked(),
+            header_address: Format::new().set_font_name(font).set_font_size(font_size),
+            sheet_header: Format::new().set_align(FormatAlign::Center).set_border(FormatBorder::Thin).set_background_color(Color::RGB(layout.header_rgb()?)).set_font_name(font).set_font_size(font_size),
+            sheet_description: Format::new().set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size),
+            sheet_hours: Format::new().set_align(FormatAlign::Center).set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size),
+            sheet_description_unlocked: Format::new().set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size).set_unlocked(),
+            sheet_hours_unlocked: Format::new().set_align(FormatAlign::Center).set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size).set_unlocked(),
+            sheet_total_description: Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Left).set_font_name(font).set_font_size(font_size),
+            sheet_rowtotal: Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Center).set_font_name(font).set_font_size(font_size),
+            sheet_daytotal: Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Center).set_font_name(font).set_font_size(font_size),
+            header_expenses: Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Left).set_font_name(font).set_font_size(font_size),
+            header_expenses_total: Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Right).set_font_name(font).set_font_size(font_size),
+            expenses_date: Format::new().set_border(FormatBorder::Thin).set_num_format("dd-mm-yyyy").set_font_name(font).set_font_size(font_size).set_unlocked(),
+            expenses_description: Format::new().set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size).set_unlocked(),
+            expenses_amount: Format::new().set_num_format("€ #,##0.00").set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size),
+            expenses_amount_unlocked: Format::new().set_num_format("€ #,##0.00").set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size).set_unlocked(),
+            expenses_total_description: Format::new().set_font_name(font).set_font_size(font_size),
+            mileage_km: Format::new().set_num_format("#,##0.0").set_border(FormatBorder::Thin).set_font_name(font).set_font_size(font_size),
+            footer_header: Format::new().set_bold().set_align(FormatAlign::Left).set_font_name(font).set_font_size(font_size),
+            footer: Format::new().set_align(FormatAlign::Left).set_font_name(font).set_font_size(font_size).set_unlocked(),
+            footer_date: Format::new().set_num_format("dd-mm-yyyy").set_font_name(font).set_font_size(font_size).set_unlocked(),
+            footer_signature: Format::new().set_bold().set_border(FormatBorder::Medium).set_align(FormatAlign::Top).set_font_name(font).set_font_size(font_size),
+        })
+    }
+}
 
-    // Prepare statement for fetching hours
-    // We cannot parameterize column names, so we prepare the logic inside the loop
+fn write_urenstaat_xlsx(urenstaat: &Urenstaat, layout: &Layout, filename: &str) -> Result<(), Box<dyn Error>> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    write_urenstaat_sheet(worksheet, urenstaat, layout)?;
+    workbook.save(filename)?;
+    Ok(())
+}
+
+// Writes the Urenstaat layout into a worksheet, so it can be used for single and multi-project workbooks
+fn write_urenstaat_sheet(worksheet: &mut Worksheet, urenstaat: &Urenstaat, layout: &Layout) -> Result<(), Box<dyn Error>> {
+    let selected_project = &urenstaat.project;
+    let selected_year = urenstaat.year;
+    let selected_month = urenstaat.month;
+    let medewerker_name = &urenstaat.employee.name;
+    let medewerker_title = &urenstaat.employee.title;
+    let medewerker_phone = &urenstaat.employee.phone;
+    let now = urenstaat.fill_date;
+    let labels = &layout.labels;
+    let rows = urenstaat.rows(layout);
+
+    worksheet.protect();
+
+    let f = SheetFormats::new(layout)?;
+
+    write_sheet_header(worksheet, layout, &f, &SheetHeader {
+        employee_name: medewerker_name,
+        employee_title: medewerker_title,
+        employee_phone: medewerker_phone,
+        client: selected_project,
+        period: (&labels.month, layout.lang.catalog().month_name(selected_month).to_string()),
+        year: selected_year,
+        fill_date: now,
+    }, rows.last)?;
+
+    // --- Calendar Grid & Data Fetching ---
+    let start_row_cal = rows.cal; 
+    let start_row_hours = rows.hours;
     
-    worksheet.write_string_with_format(start_row_hours, 1, "Gewerkte uren", &sheet_description_fmt)?;
+    let lines = activity_lines(urenstaat, layout);
+    let line_count = rows.total - start_row_hours;
 
     for day in 1..=31 {
         let col_idx = day as u16;
@@ -639,165 +1464,320 @@ fn export_timesheet(conn: &Connection) -> Result<(), Box<dyn Error>> {
         if let Some(date) = NaiveDate::from_ymd_opt(selected_year, selected_month, day) {
             
             // Write Calendar Header
-            worksheet.write_string_with_format(start_row_cal, col_idx+1, days_map(date.weekday()), &sheet_header_fmt)?;
-            worksheet.write_number_with_format(start_row_cal + 1, col_idx+1, day, &sheet_header_fmt)?;
-
-            // Fetch Data
-            let iso_week = date.iso_week();
-            // Create week string (Note: iso_week.year() handles year crossover, e.g., Dec 30 might be Week 1 of next year)
-            let week_str = format!("{}-W{:02}", iso_week.year(), iso_week.week());
-            
-            // Map weekday to DB column
-            let col_name = match date.weekday() {
-                Weekday::Mon => "mon", Weekday::Tue => "tue", Weekday::Wed => "wed",
-                Weekday::Thu => "thu", Weekday::Fri => "fri", Weekday::Sat => "sat", Weekday::Sun => "sun",
-            };
-
-            let sql = format!("SELECT {} FROM timesheets WHERE week = ?1 AND project = ?2", col_name);
-            
-            let hours: Option<f64> = conn.query_row(
-                &sql, 
-                params![week_str, selected_project], 
-                |row| row.get(0)
-            ).optional()?; // Returns Ok(None) if no row found
-
-            let val = hours.unwrap_or(0.0);
-            
-            // Write Hours (Row 16, typically index 0 in the 5 blank rows)
-            if val > 0.0 {
-                worksheet.write_number_with_format(start_row_hours, col_idx+1, val, &sheet_hours_fmt)?;
-            } else {
-                worksheet.write_blank(start_row_hours, col_idx+1, &sheet_hours_fmt)?;
-            }
-
+            worksheet.write_string_with_format(start_row_cal, col_idx+1, layout.lang.catalog().weekday_short(date.weekday()), &f.sheet_header)?;
+            worksheet.write_number_with_format(start_row_cal + 1, col_idx+1, day, &f.sheet_header)?;
         } else {
             // Invalid date (e.g. Feb 30), just format blank
-             worksheet.write_blank(start_row_cal, col_idx+1, &sheet_header_fmt)?;
-             worksheet.write_blank(start_row_cal+1, col_idx+1, &sheet_header_fmt)?;
-             worksheet.write_blank(start_row_hours, col_idx+1, &sheet_hours_fmt)?;
+             worksheet.write_blank(start_row_cal, col_idx+1, &f.sheet_header)?;
+             worksheet.write_blank(start_row_cal+1, col_idx+1, &f.sheet_header)?;
         }
     }
 
-    // Fill the remaining 4 empty rows just for visuals
-    for r in 1..5 {
-        worksheet.write_blank(start_row_hours + r, 1, &sheet_description_unlocked_fmt)?;
-        for c in 2..=32 {
-             worksheet.write_blank(start_row_hours + r, c, &sheet_hours_unlocked_fmt)?;
+    // A line per activity, then empty unlocked lines to fill in by hand
+    for r in 0..line_count {
+        let row = start_row_hours + r;
+        match lines.get(r as usize) {
+            Some((label, hours)) => {
+                worksheet.write_string_with_format(row, 1, label, &f.sheet_description)?;
+                for day in 1..=31 {
+                    let val = hours.get(&day).copied().unwrap_or(0.0);
+                    if val > 0.0 {
+                        worksheet.write_number_with_format(row, day as u16 + 1, val, &f.sheet_hours)?;
+                    } else {
+                        worksheet.write_blank(row, day as u16 + 1, &f.sheet_hours)?;
+                    }
+                }
+            }
+            None => {
+                worksheet.write_blank(row, 1, &f.sheet_description_unlocked)?;
+                for c in 2..=32 {
+                     worksheet.write_blank(row, c, &f.sheet_hours_unlocked)?;
+                }
+            }
         }
     }
 
     // --- Totals Formulas ---
-    worksheet.write_string_with_format(start_row_cal + 1, 33, "Totaal", &sheet_rowtotal_fmt)?;
+    worksheet.write_string_with_format(start_row_cal + 1, 33, &labels.total, &f.sheet_rowtotal)?;
     
     // Row Totals
-    for r in 0..5 {
+    for r in 0..line_count {
         let current_row = start_row_hours + r;
         let row_num_excel = current_row + 1;
-        let formula = format!("=SUM(B{}:AF{})", row_num_excel, row_num_excel);
-        worksheet.write_formula_with_format(current_row, 33, Formula::new(formula), &sheet_rowtotal_fmt)?;
+        let formula = format!("=SUM(C{}:AG{})", row_num_excel, row_num_excel);
+        worksheet.write_formula_with_format(current_row, 33, Formula::new(formula), &f.sheet_rowtotal)?;
     }
 
     // Column Totals
-    let total_facturabel_row = start_row_hours + 5;
-    worksheet.write_string_with_format(total_facturabel_row, 1, "Totaal facturabel", &sheet_total_description_fmt)?;
+    let total_facturabel_row = rows.total;
+    worksheet.write_string_with_format(total_facturabel_row, 1, &labels.total_billable, &f.sheet_total_description)?;
 
     for c in 2..=32 {
         let col_char = column_number_to_name(c); 
         let formula = format!("=SUM({}{}:{}{})", col_char, start_row_hours + 1, col_char, total_facturabel_row);
-        worksheet.write_formula_with_format(total_facturabel_row, c, Formula::new(formula), &sheet_daytotal_fmt)?;
+        worksheet.write_formula_with_format(total_facturabel_row, c, Formula::new(formula), &f.sheet_daytotal)?;
     }
     
     // Grand Total
     let formula_grand = format!("=SUM(AH{}:AH{})", start_row_hours + 1, total_facturabel_row);
-    worksheet.write_formula_with_format(total_facturabel_row, 33, Formula::new(formula_grand), &sheet_rowtotal_fmt)?;
+    worksheet.write_formula_with_format(total_facturabel_row, 33, Formula::new(formula_grand), &f.sheet_rowtotal)?;
+
+    let non_billable: Vec<f64> = (1..=31).map(|day| urenstaat.non_billable.get(&day).copied().unwrap_or(0.0)).collect();
+    write_non_billable_row(worksheet, labels, &f, total_facturabel_row + 1, 2, &non_billable)?;
 
     // --- Expenses (Same as original) ---
-    let expense_start_row = total_facturabel_row + 3;
-    worksheet.write_string_with_format(expense_start_row, 1, "Onkostendeclaratie medewerker (bonnen bijvoegen)", &footer_header_fmt)?;
-    let exp_header_row = expense_start_row + 1;
+    if let Some(expense_start_row) = rows.expenses {
+        write_expenses_block(worksheet, labels, &f, expense_start_row, &urenstaat.expenses)?;
+    }
+
+    if let Some(mileage_row) = rows.mileage {
+        write_mileage_block(worksheet, layout, &f, mileage_row, &urenstaat.trips)?;
+    }
 
-    worksheet.merge_range(exp_header_row, 1, exp_header_row, 2, "Datum", &header_expenses_fmt)?;
-    worksheet.merge_range(exp_header_row, 3, exp_header_row, 22, "Omschrijving", &header_expenses_fmt)?;
-    worksheet.merge_range(exp_header_row, 23, exp_header_row, 26, "Bedrag excl. BTW", &header_expenses_total_fmt)?;
-    worksheet.merge_range(exp_header_row, 27, exp_header_row, 29, "BTW", &header_expenses_total_fmt)?;
-    worksheet.merge_range(exp_header_row, 30, exp_header_row, 33, "Bedrag incl. BTW", &header_expenses_total_fmt)?;
+    // --- Signatures ---
+    if let Some(sign_row) = rows.signatures {
+        write_signature_block(worksheet, layout, &f, sign_row, selected_project, &urenstaat.employee, now, urenstaat.approval.as_ref())?;
+    }
 
-    for i in 0..4 {
-        let r = exp_header_row + 1 + i;
+    Ok(())
+}
+
+// Label and billable hours per activity line of the monthly sheet; hours without a category
+// are "Gewerkte uren", as on the standard form
+fn activity_lines(urenstaat: &Urenstaat, layout: &Layout) -> Vec<(String, BTreeMap<u32, f64>)> {
+    let label = |category: &str| if category.is_empty() { layout.labels.worked_hours.clone() } else { categories::label(layout.lang.catalog(), category) };
+    let mut lines: Vec<(String, BTreeMap<u32, f64>)> = urenstaat.activities.iter().map(|(category, hours)| (label(category), hours.clone())).collect();
+    if lines.is_empty() {
+        lines.push((label(""), BTreeMap::new()));
+    }
+    lines
+}
+
+// The non-billable hours on the row below "Totaal facturabel", outside its formulas; left out
+// when there are none. `hours` holds the day columns from `first_col` on.
+fn write_non_billable_row(worksheet: &mut Worksheet, labels: &Labels, f: &SheetFormats, row: u32, first_col: u16, hours: &[f64]) -> Result<(), Box<dyn Error>> {
+    if hours.iter().all(|h| *h == 0.0) {
+        return Ok(());
+    }
+    worksheet.write_string_with_format(row, 1, &labels.non_billable, &f.sheet_description)?;
+    for (i, &h) in hours.iter().enumerate() {
+        let col = first_col + i as u16;
+        if h > 0.0 {
+            worksheet.write_number_with_format(row, col, h, &f.sheet_hours)?;
+        } else {
+            worksheet.write_blank(row, col, &f.sheet_hours)?;
+        }
+    }
+    let last_col = first_col + hours.len() as u16 - 1;
+    let formula = format!("=SUM({}{}:{}{})", column_number_to_name(first_col), row + 1, column_number_to_name(last_col), row + 1);
+    worksheet.write_formula_with_format(row, 33, Formula::new(formula), &f.sheet_rowtotal)?;
+    Ok(())
+}
+
+// Header fields that differ between the monthly and the weekly sheet
+struct SheetHeader<'a> {
+    employee_name: &'a str,
+    employee_title: &'a str,
+    employee_phone: &'a str,
+    client: &'a str,
+    period: (&'a str, String), // label and value of the first period row, e.g. month or week
+    year: i32,
+    fill_date: NaiveDate,
+}
+
+// Page setup, title, employee/assignment/period blocks, logo and address
+fn write_sheet_header(worksheet: &mut Worksheet, layout: &Layout, f: &SheetFormats, header: &SheetHeader, last_row: u32) -> Result<(), Box<dyn Error>> {
+    let labels = &layout.labels;
+
+    // Layout
+    worksheet.set_landscape();
+
+    worksheet.set_print_area(0, 0, last_row, 33)?;
+    worksheet.set_print_gridlines(false);
+    worksheet.set_paper_size(9); // A4
+    worksheet.set_margins(0.25, 0.25, 0.5, 0.5, 0.25, 0.25);
+    worksheet.set_print_fit_to_pages(1,1);
+
+    for col in 0..=32 { worksheet.set_column_width(col, 6)?; }
+    worksheet.set_column_width(1, 20)?; 
+    worksheet.set_column_width(33, 10)?;
+
+    // --- Write Headers ---
+    worksheet.write_string_with_format(1, 1, &labels.title, &f.title)?;
+
+    if layout.blocks.employee {
+        worksheet.write_string_with_format(3, 1, &labels.employee_name, &f.header)?;
+        worksheet.merge_range(3, 2, 3, 9, header.employee_name,&f.header)?;
+
+        worksheet.write_string_with_format(4, 1, &labels.employee_title, &f.header)?;
+        worksheet.merge_range(4, 2, 4, 9, header.employee_title,&f.header_unlocked)?;
+        worksheet.write_string_with_format(5, 1, &labels.employee_phone, &f.header)?;
+        worksheet.merge_range(5, 2, 5, 9, header.employee_phone,&f.header_unlocked)?;
+    }
+
+    if layout.blocks.assignment {
+        worksheet.write_string_with_format(7, 1, &labels.client, &f.header)?;
+        worksheet.merge_range(7, 2, 7, 9, header.client,&f.header_unlocked)?;
+        worksheet.write_string_with_format(8, 1, &labels.role, &f.header)?;
+        worksheet.merge_range(8, 2, 8, 9, "",&f.header_unlocked)?;
+        worksheet.write_string_with_format(9, 1, &labels.project_name, &f.header)?;
+        worksheet.merge_range(9, 2, 9, 9, "",&f.header_unlocked)?;
+        worksheet.write_string_with_format(10, 1, &labels.project_number, &f.header)?;
+        worksheet.merge_range(10, 2, 10, 9, "",&f.header_unlocked)?;
+    }
+ 
+    if layout.blocks.period {
+        let (period_label, period) = &header.period;
+
+        worksheet.merge_range(3, 12, 3, 15, period_label,&f.header)?;
+        worksheet.merge_range(4, 12, 4, 15, &labels.year,&f.header)?;
+        worksheet.merge_range(5, 12, 5, 15, &labels.fill_date,&f.header)?;
+
+        worksheet.merge_range(3, 16, 3, 20, period,&f.header)?;
+        worksheet.merge_range(4, 16, 4, 20, &header.year.to_string(),&f.header)?;
+        worksheet.merge_range(5, 16, 5, 20, &header.fill_date.format("%d-%m-%Y").to_string(),&f.header_unlocked)?;
+    }
+
+    if !layout.logo.is_empty() {
+        let logo_image = Image::new(&layout.logo)?;
+        let logo_image = logo_image.set_scale_to_size(300, 200, true);
 
-        worksheet.merge_range(r, 1, r, 2, "", &expenses_date_fmt)?;
-        worksheet.merge_range(r, 3, r, 22, "", &expenses_description_fmt)?;
-        worksheet.merge_range(r, 23, r, 26, "", &expenses_amount_fmt)?;
-        worksheet.merge_range(r, 27, r, 29, "", &expenses_amount_fmt)?;
-        worksheet.merge_range(r, 30, r, 33, "", &expenses_amount_unlocked_fmt)?;
+        worksheet.insert_image(2, 23, &logo_image)?;
+    }
+
+    for (i, line) in layout.address_lines.iter().enumerate() {
+        worksheet.write_string_with_format(7 + i as u32, 23, line, &f.header_address)?;
+    }
 
-        worksheet.write_number_with_format(r, 23, 0, &expenses_amount_fmt)?; 
-        worksheet.write_number_with_format(r, 27, 0, &expenses_amount_fmt)?; 
-        worksheet.write_number_with_format(r, 27, 0, &expenses_amount_unlocked_fmt)?; 
+    Ok(())
+}
+
+// The registered expenses of the sheet, then empty lines to fill in by hand up to 4; the
+// amounts incl. VAT stay editable and the amounts excl. VAT and the VAT follow from them
+fn write_expenses_block(worksheet: &mut Worksheet, labels: &Labels, f: &SheetFormats, expense_start_row: u32, expenses: &[expenses::Expense]) -> Result<(), Box<dyn Error>> {
+    worksheet.write_string_with_format(expense_start_row, 1, &labels.expenses, &f.footer_header)?;
+    let exp_header_row = expense_start_row + 1;
+    let lines = expenses.len().max(4) as u32;
+
+    worksheet.merge_range(exp_header_row, 1, exp_header_row, 2, &labels.expense_date, &f.header_expenses)?;
+    worksheet.merge_range(exp_header_row, 3, exp_header_row, 22, &labels.expense_description, &f.header_expenses)?;
+    worksheet.merge_range(exp_header_row, 23, exp_header_row, 26, &labels.expense_excl_vat, &f.header_expenses_total)?;
+    worksheet.merge_range(exp_header_row, 27, exp_header_row, 29, &labels.expense_vat, &f.header_expenses_total)?;
+    worksheet.merge_range(exp_header_row, 30, exp_header_row, 33, &labels.expense_incl_vat, &f.header_expenses_total)?;
+
+    for i in 0..lines {
+        let r = exp_header_row + 1 + i;
+        let expense = expenses.get(i as usize);
+        let date = expense.map(|e| e.date.format("%d-%m-%Y").to_string()).unwrap_or_default();
+
+        worksheet.merge_range(r, 1, r, 2, &date, &f.expenses_date)?;
+        worksheet.merge_range(r, 3, r, 22, expense.map(|e| e.description.as_str()).unwrap_or_default(), &f.expenses_description)?;
+        worksheet.merge_range(r, 23, r, 26, "", &f.expenses_amount)?;
+        worksheet.merge_range(r, 27, r, 29, "", &f.expenses_amount)?;
+        worksheet.merge_range(r, 30, r, 33, "", &f.expenses_amount_unlocked)?;
+
+        worksheet.write_number_with_format(r, 23, 0, &f.expenses_amount)?; 
+        worksheet.write_number_with_format(r, 27, 0, &f.expenses_amount)?; 
+        worksheet.write_number_with_format(r, 27, 0, &f.expenses_amount_unlocked)?; 
+        if let Some(expense) = expense {
+            worksheet.write_number_with_format(r, 30, expense.amount, &f.expenses_amount_unlocked)?;
+        }
 
         let row_excel = r + 1;
         let formula_incl = format!("=AE{}/121*100", row_excel);
-        worksheet.write_formula_with_format(r, 23, Formula::new(formula_incl), &expenses_amount_fmt)?;
+        worksheet.write_formula_with_format(r, 23, Formula::new(formula_incl), &f.expenses_amount)?;
         let formula_incl = format!("=AE{}/121*21", row_excel);
-        worksheet.write_formula_with_format(r, 27, Formula::new(formula_incl), &expenses_amount_fmt)?;
+        worksheet.write_formula_with_format(r, 27, Formula::new(formula_incl), &f.expenses_amount)?;
     }
 
-    let exp_total_row = exp_header_row + 5;
-    worksheet.write_string_with_format(exp_total_row, 3, "Totaal", &expenses_total_description_fmt)?;
+    let exp_total_row = exp_header_row + 1 + lines;
+    worksheet.write_string_with_format(exp_total_row, 3, &labels.expense_total, &f.expenses_total_description)?;
     let start_sum = exp_header_row + 2; 
     let end_sum = exp_total_row;  
-    worksheet.merge_range(exp_total_row, 23, exp_total_row, 26, "", &expenses_amount_fmt)?;
-    worksheet.merge_range(exp_total_row, 27, exp_total_row, 29, "", &expenses_amount_fmt)?;
-    worksheet.merge_range(exp_total_row, 30, exp_total_row, 33, "", &expenses_amount_fmt)?;      
-    worksheet.write_formula_with_format(exp_total_row, 23, Formula::new(format!("=SUM(X{}:X{})", start_sum, end_sum)), &expenses_amount_fmt)?;
-    worksheet.write_formula_with_format(exp_total_row, 27, Formula::new(format!("=SUM(AB{}:AB{})", start_sum, end_sum)), &expenses_amount_fmt)?;
-    worksheet.write_formula_with_format(exp_total_row, 30, Formula::new(format!("=SUM(AE{}:AE{})", start_sum, end_sum)), &expenses_amount_fmt)?;
+    worksheet.merge_range(exp_total_row, 23, exp_total_row, 26, "", &f.expenses_amount)?;
+    worksheet.merge_range(exp_total_row, 27, exp_total_row, 29, "", &f.expenses_amount)?;
+    worksheet.merge_range(exp_total_row, 30, exp_total_row, 33, "", &f.expenses_amount)?;      
+    worksheet.write_formula_with_format(exp_total_row, 23, Formula::new(format!("=SUM(X{}:X{})", start_sum, end_sum)), &f.expenses_amount)?;
+    worksheet.write_formula_with_format(exp_total_row, 27, Formula::new(format!("=SUM(AB{}:AB{})", start_sum, end_sum)), &f.expenses_amount)?;
+    worksheet.write_formula_with_format(exp_total_row, 30, Formula::new(format!("=SUM(AE{}:AE{})", start_sum, end_sum)), &f.expenses_amount)?;
+    Ok(())
+}
 
-    // --- Signatures ---
-    let sign_row = exp_total_row + 3;
-    worksheet.write_string_with_format(sign_row, 1, "Opdrachtgever:", &footer_header_fmt)?;
-    worksheet.write_string_with_format(sign_row + 1, 1, &selected_project, &footer_fmt)?;
-    worksheet.write_string_with_format(sign_row + 2, 1, "Datum:", &footer_header_fmt)?;
-    worksheet.write_string_with_format(sign_row + 3, 1, now.format("%d-%m-%Y").to_string(), &footer_date_fmt)?;
+// The trips of the project in the month below the expenses, with the same columns: the
+// allowance is the kilometers times the rate stored with the trip
+fn write_mileage_block(worksheet: &mut Worksheet, layout: &Layout, f: &SheetFormats, mileage_start_row: u32, trips: &[travel::Trip]) -> Result<(), Box<dyn Error>> {
+    let labels = &layout.labels;
+    worksheet.write_string_with_format(mileage_start_row, 1, &labels.mileage, &f.footer_header)?;
+    let header_row = mileage_start_row + 1;
+
+    worksheet.merge_range(header_row, 1, header_row, 2, &labels.expense_date, &f.header_expenses)?;
+    worksheet.merge_range(header_row, 3, header_row, 10, &labels.mileage_from, &f.header_expenses)?;
+    worksheet.merge_range(header_row, 11, header_row, 18, &labels.mileage_to, &f.header_expenses)?;
+    worksheet.merge_range(header_row, 19, header_row, 22, &labels.mileage_mode, &f.header_expenses)?;
+    worksheet.merge_range(header_row, 23, header_row, 26, &labels.mileage_km, &f.header_expenses_total)?;
+    worksheet.merge_range(header_row, 27, header_row, 29, &labels.mileage_rate, &f.header_expenses_total)?;
+    worksheet.merge_range(header_row, 30, header_row, 33, &labels.mileage_amount, &f.header_expenses_total)?;
+
+    for (i, trip) in trips.iter().enumerate() {
+        let r = header_row + 1 + i as u32;
+        worksheet.merge_range(r, 1, r, 2, &trip.date.format("%d-%m-%Y").to_string(), &f.sheet_description)?;
+        worksheet.merge_range(r, 3, r, 10, &trip.origin, &f.sheet_description)?;
+        worksheet.merge_range(r, 11, r, 18, &trip.destination, &f.sheet_description)?;
+        worksheet.merge_range(r, 19, r, 22, trip.mode.label(layout.lang.catalog()), &f.sheet_description)?;
+        worksheet.merge_range(r, 23, r, 26, "", &f.mileage_km)?;
+        worksheet.merge_range(r, 27, r, 29, "", &f.expenses_amount)?;
+        worksheet.merge_range(r, 30, r, 33, "", &f.expenses_amount)?;
+
+        worksheet.write_number_with_format(r, 23, trip.km, &f.mileage_km)?;
+        worksheet.write_number_with_format(r, 27, trip.rate, &f.expenses_amount)?;
+        let formula = Formula::new(format!("=X{}*AB{}", r + 1, r + 1)).set_result(trip.allowance().to_string());
+        worksheet.write_formula_with_format(r, 30, formula, &f.expenses_amount)?;
+    }
 
+    let total_row = header_row + 1 + trips.len() as u32;
+    let (start_sum, end_sum) = (header_row + 2, total_row);
+    worksheet.write_string_with_format(total_row, 3, &labels.expense_total, &f.expenses_total_description)?;
+    worksheet.merge_range(total_row, 23, total_row, 26, "", &f.mileage_km)?;
+    worksheet.merge_range(total_row, 30, total_row, 33, "", &f.expenses_amount)?;
+    worksheet.write_formula_with_format(total_row, 23, Formula::new(format!("=SUM(X{}:X{})", start_sum, end_sum)), &f.mileage_km)?;
+    worksheet.write_formula_with_format(total_row, 30, Formula::new(format!("=SUM(AE{}:AE{})", start_sum, end_sum)), &f.expenses_amount)?;
+    Ok(())
+}
 
-    worksheet.write_string_with_format(sign_row, 23, "Medewerker:", &footer_header_fmt)?;
-    worksheet.write_string_with_format(sign_row + 1, 23, &medewerker_name, &footer_fmt)?;
-    worksheet.write_string_with_format(sign_row + 2, 23, "Datum:", &footer_header_fmt)?;
-    worksheet.write_string_with_format(sign_row + 3, 23, now.format("%d-%m-%Y").to_string(), &footer_date_fmt)?;
+// With an approval, the client's side carries the reviewer's name and the date of approval
+#[allow(clippy::too_many_arguments)]
+fn write_signature_block(worksheet: &mut Worksheet, layout: &Layout, f: &SheetFormats, sign_row: u32, client: &str, employee: &Employee, date: NaiveDate, approval: Option<&lock::Approval>) -> Result<(), Box<dyn Error>> {
+    let labels = &layout.labels;
+    let client_date = approval.map(|a| a.date).unwrap_or(date);
 
+    worksheet.write_string_with_format(sign_row, 1, &labels.sign_client, &f.footer_header)?;
+    worksheet.write_string_with_format(sign_row + 1, 1, client, &f.footer)?;
+    worksheet.write_string_with_format(sign_row + 2, 1, &labels.sign_date, &f.footer_header)?;
+    worksheet.write_string_with_format(sign_row + 3, 1, client_date.format("%d-%m-%Y").to_string(), &f.footer_date)?;
 
-    worksheet.write_string_with_format(sign_row + 4, 1, "Handtekening opdrachtgever:", &footer_header_fmt)?;
-    worksheet.write_string_with_format(sign_row + 4, 23, "Handtekening medewerker:", &footer_header_fmt)?;
-    worksheet.set_row_height(sign_row+5, 120)?;
-    worksheet.merge_range(sign_row+5, 1, sign_row+5, 9, "", &footer_signature_fmt)?;
-    worksheet.merge_range(sign_row+5, 23, sign_row+5, 32, "", &footer_signature_fmt)?;  
 
-    let signature = Image::new("signature.png")?;
-    let signature = signature.set_scale_to_size(300, 200, true);
+    worksheet.write_string_with_format(sign_row, 23, &labels.sign_employee, &f.footer_header)?;
+    worksheet.write_string_with_format(sign_row + 1, 23, &employee.name, &f.footer)?;
+    worksheet.write_string_with_format(sign_row + 2, 23, &labels.sign_date, &f.footer_header)?;
+    worksheet.write_string_with_format(sign_row + 3, 23, date.format("%d-%m-%Y").to_string(), &f.footer_date)?;
 
-    worksheet.insert_image(sign_row + 5, 23, &signature)?;
 
-    workbook.save(&filename)?;
+    worksheet.write_string_with_format(sign_row + 4, 1, &labels.signature_client, &f.footer_header)?;
+    worksheet.write_string_with_format(sign_row + 4, 23, &labels.signature_employee, &f.footer_header)?;
+    worksheet.set_row_height(sign_row+5, 120)?;
+    let approved = approval
+        .map(|a| Catalog::fill(&labels.approved_by, &[&a.reviewer, &a.date.format("%d-%m-%Y")]))
+        .unwrap_or_default();
+    worksheet.merge_range(sign_row+5, 1, sign_row+5, 9, &approved, &f.footer_signature)?;
+    worksheet.merge_range(sign_row+5, 23, sign_row+5, 32, "", &f.footer_signature)?;  
+
+    let signature = employee.signature(layout);
+    if !signature.is_empty() {
+        let signature = Image::new(signature)?;
+        let signature = signature.set_scale_to_size(300, 200, true);
+
+        worksheet.insert_image(sign_row + 5, 23, &signature)?;
+    }
 
-    println!("File successfully generated: {}", filename);
     Ok(())
 }
-
-fn month_name(month_num: u32) -> &'static str {
-    match month_num {
-        1 => "Januari",
-        2 => "Februari",
-        3 => "Maart",
-        4 => "April",
-        5 => "Mei",
-        6 => "Juni",
-        7 => "Juli",
-        8 => "Augustus",
-        9 => "September",
-        10 => "Oktober",
-        11 => "November",
-        12 => "December",
-        _ => "Onbekend",
-    }
-}
@@ -0,0 +1,423 @@
+use chrono::{Datelike, NaiveDate};
+use printpdf::{
+    image_crate, path::PaintMode, BuiltinFont, Color, Image, ImageTransform, IndirectFontRef, Mm,
+    PdfDocument, PdfLayerReference, Pt, Rect, Rgb,
+};
+use std::{error::Error, fs::File, io::BufWriter};
+
+use crate::{activity_lines, format_hours, layout::Layout, locale::Catalog, Urenstaat};
+
+// A4 landscape with the same margins as the Excel print setup (inches)
+const PAGE_WIDTH: f32 = 297.0;
+const PAGE_HEIGHT: f32 = 210.0;
+const MARGIN_X: f32 = 0.25 * 25.4;
+const MARGIN_Y: f32 = 0.5 * 25.4;
+
+// Print area of the worksheet: columns 0..=33, rows up to Rows::last
+const COLS: usize = 34;
+const DEFAULT_ROW_HEIGHT: f32 = 15.0;
+
+const THIN: f32 = 0.5;
+const MEDIUM: f32 = 1.5;
+
+#[derive(Clone, Copy)]
+enum Align { Left, Center, Right }
+
+#[derive(Clone, Copy)]
+struct TextStyle { title: bool, bold: bool, align: Align }
+
+const TITLE: TextStyle = TextStyle { title: true, bold: true, align: Align::Left };
+const PLAIN: TextStyle = TextStyle { title: false, bold: false, align: Align::Left };
+const BOLD: TextStyle = TextStyle { title: false, bold: true, align: Align::Left };
+const CENTERED: TextStyle = TextStyle { title: false, bold: false, align: Align::Center };
+const CENTERED_BOLD: TextStyle = TextStyle { title: false, bold: true, align: Align::Center };
+const AMOUNT: TextStyle = TextStyle { title: false, bold: false, align: Align::Right };
+const AMOUNT_BOLD: TextStyle = TextStyle { title: false, bold: true, align: Align::Right };
+
+// Maps worksheet rows/columns onto the PDF page, so the layout can be written
+// with the same coordinates as the xlsx renderer.
+struct Sheet {
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    col_x: Vec<f32>, // left edge per column, in unscaled points
+    row_y: Vec<f32>, // top edge per row, in unscaled points
+    scale: f32,
+    font_size: f32,
+    title_font_size: f32,
+}
+
+impl Sheet {
+    fn new(layer: PdfLayerReference, regular: IndirectFontRef, bold: IndirectFontRef, layout: &Layout, rows: usize, row_heights: &[(usize, f32)]) -> Self {
+        // Excel column widths are in characters; convert them to points like Excel does (7px per char + 5px padding)
+        let col_width = |c: usize| -> f32 {
+            let chars = match c { 1 => 20.0, 33 => 10.0, _ => 6.0 };
+            (chars * 7.0 + 5.0) * 0.75
+        };
+        let row_height = |r: usize| -> f32 {
+            row_heights.iter().find(|(row, _)| *row == r).map(|(_, h)| *h).unwrap_or(DEFAULT_ROW_HEIGHT)
+        };
+
+        let mut col_x = vec![0.0];
+        for c in 0..COLS { col_x.push(col_x[c] + col_width(c)); }
+        let mut row_y = vec![0.0];
+        for r in 0..rows { row_y.push(row_y[r] + row_height(r)); }
+
+        // Fit to one page, like set_print_fit_to_pages(1, 1)
+        let avail_w = Pt::from(Mm(PAGE_WIDTH - 2.0 * MARGIN_X)).0;
+        let avail_h = Pt::from(Mm(PAGE_HEIGHT - 2.0 * MARGIN_Y)).0;
+        let scale = (avail_w / col_x[COLS]).min(avail_h / row_y[rows]).min(1.0);
+
+        Sheet {
+            layer, regular, bold, col_x, row_y, scale,
+            font_size: layout.font_size as f32,
+            title_font_size: layout.title_font_size as f32,
+        }
+    }
+
+    fn x(&self, col: usize) -> Mm {
+        Mm(MARGIN_X) + Mm::from(Pt(self.col_x[col] * self.scale))
+    }
+
+    fn y(&self, row: usize) -> Mm {
+        Mm(PAGE_HEIGHT - MARGIN_Y) - Mm::from(Pt(self.row_y[row] * self.scale))
+    }
+
+    fn rect(&self, r1: usize, c1: usize, r2: usize, c2: usize) -> Rect {
+        Rect::new(self.x(c1), self.y(r2 + 1), self.x(c2 + 1), self.y(r1))
+    }
+
+    fn border(&self, r1: usize, c1: usize, r2: usize, c2: usize, thickness: f32) {
+        self.layer.set_outline_thickness(thickness * self.scale);
+        self.layer.add_rect(self.rect(r1, c1, r2, c2).with_mode(PaintMode::Stroke));
+    }
+
+    fn fill(&self, r1: usize, c1: usize, r2: usize, c2: usize, color: u32) {
+        self.layer.set_fill_color(rgb(color));
+        self.layer.add_rect(self.rect(r1, c1, r2, c2).with_mode(PaintMode::Fill));
+        self.layer.set_fill_color(rgb(0x000000));
+    }
+
+    // Writes text in the (merged) range c1..=c2 of a row
+    fn text(&self, row: usize, c1: usize, c2: usize, text: &str, style: TextStyle) {
+        if text.is_empty() { return; }
+        let size = if style.title { self.title_font_size } else { self.font_size } * self.scale;
+        let padding = Mm::from(Pt(2.0 * self.scale));
+        // Helvetica averages roughly half an em per character
+        let width = Mm::from(Pt(text.chars().count() as f32 * size * 0.5));
+        let x = match style.align {
+            Align::Left => self.x(c1) + padding,
+            Align::Center => Mm((self.x(c1).0 + self.x(c2 + 1).0 - width.0) / 2.0),
+            Align::Right => self.x(c2 + 1) - padding - width,
+        };
+        let y = self.y(row + 1) + Mm::from(Pt(DEFAULT_ROW_HEIGHT * self.scale * 0.3));
+        let font = if style.bold { &self.bold } else { &self.regular };
+        self.layer.use_text(text, size, x, y, font);
+    }
+
+    // Places an image with its top-left corner at (row, col), scaled to fit max_w x max_h pixels
+    fn image(&self, path: &str, row: usize, col: usize, max_w: f32, max_h: f32) -> Result<(), Box<dyn Error>> {
+        let dynamic = image_crate::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        let (w, h) = (dynamic.width() as f32, dynamic.height() as f32);
+        let fit = (max_w / w).min(max_h / h);
+
+        // At 72 dpi one pixel is one point, which keeps the scale factors simple
+        let factor = fit * 0.75 * self.scale;
+        let height = Mm::from(Pt(h * factor));
+        Image::from_dynamic_image(&dynamic).add_to_layer(
+            self.layer.clone(),
+            ImageTransform {
+                translate_x: Some(self.x(col)),
+                translate_y: Some(self.y(row) - height),
+                scale_x: Some(factor),
+                scale_y: Some(factor),
+                dpi: Some(72.0),
+                ..Default::default()
+            },
+        );
+        Ok(())
+    }
+}
+
+fn rgb(color: u32) -> Color {
+    let channel = |shift: u32| ((color >> shift) & 0xFF) as f32 / 255.0;
+    Color::Rgb(Rgb::new(channel(16), channel(8), channel(0), None))
+}
+
+fn format_euro(amount: f64) -> String {
+    format!("€ {:.2}", amount).replace('.', ",")
+}
+
+// --- Urenstaat as PDF (same layout as the xlsx export) ---
+// The PDF uses the built-in Helvetica font; `font_name` from the layout only applies to the xlsx.
+pub fn write_urenstaat_pdf(urenstaat: &Urenstaat, layout: &Layout, filename: &str) -> Result<(), Box<dyn Error>> {
+    let labels = &layout.labels;
+    let catalog = layout.lang.catalog();
+    let rows = urenstaat.rows(layout);
+    let header_color = layout.header_rgb()?;
+
+    let title = format!("Urenstaat {} {} {}", urenstaat.project, catalog.month_name(urenstaat.month), urenstaat.year);
+    let (doc, page, layer) = PdfDocument::new(&title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Urenstaat");
+    let regular = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+    let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+
+    let start_row_cal = rows.cal as usize;
+    let start_row_hours = rows.hours as usize;
+    let total_facturabel_row = rows.total as usize;
+
+    let mut row_heights = vec![(1, 18.0)];
+    if let Some(sign_row) = rows.signatures {
+        row_heights.push((sign_row as usize + 5, 120.0));
+    }
+    let sheet = Sheet::new(
+        doc.get_page(page).get_layer(layer),
+        regular,
+        bold,
+        layout,
+        rows.last as usize + 1,
+        &row_heights,
+    );
+    let fill_date = urenstaat.fill_date.format("%d-%m-%Y").to_string();
+
+    // --- Headers ---
+    sheet.text(1, 1, 9, &labels.title, TITLE);
+
+    let mut header_block = Vec::new();
+    if layout.blocks.employee {
+        header_block.extend([
+            (3, &labels.employee_name, urenstaat.employee.name.as_str()),
+            (4, &labels.employee_title, urenstaat.employee.title.as_str()),
+            (5, &labels.employee_phone, urenstaat.employee.phone.as_str()),
+        ]);
+    }
+    if layout.blocks.assignment {
+        header_block.extend([
+            (7, &labels.client, urenstaat.project.as_str()),
+            (8, &labels.role, ""),
+            (9, &labels.project_name, ""),
+            (10, &labels.project_number, ""),
+        ]);
+    }
+    for (row, label, value) in header_block {
+        sheet.border(row, 1, row, 1, THIN);
+        sheet.text(row, 1, 1, label, PLAIN);
+        sheet.border(row, 2, row, 9, THIN);
+        sheet.text(row, 2, 9, value, PLAIN);
+    }
+
+    if layout.blocks.period {
+        let year = urenstaat.year.to_string();
+        let period_block = [
+            (3, &labels.month, catalog.month_name(urenstaat.month)),
+            (4, &labels.year, year.as_str()),
+            (5, &labels.fill_date, fill_date.as_str()),
+        ];
+        for (row, label, value) in period_block {
+            sheet.border(row, 12, row, 15, THIN);
+            sheet.text(row, 12, 15, label, PLAIN);
+            sheet.border(row, 16, row, 20, THIN);
+            sheet.text(row, 16, 20, value, PLAIN);
+        }
+    }
+
+    if !layout.logo.is_empty() {
+        sheet.image(&layout.logo, 2, 23, 300.0, 200.0)?;
+    }
+    for (i, line) in layout.address_lines.iter().enumerate() {
+        sheet.text(7 + i, 23, 33, line, PLAIN);
+    }
+
+    // --- Calendar Grid ---
+    let lines = activity_lines(urenstaat, layout);
+    let line_count = total_facturabel_row - start_row_hours;
+    let mut day_totals = [0.0; 31];
+
+    for day in 1..=31u32 {
+        let col = day as usize + 1;
+        sheet.fill(start_row_cal, col, start_row_cal + 1, col, header_color);
+        sheet.border(start_row_cal, col, start_row_cal, col, THIN);
+        sheet.border(start_row_cal + 1, col, start_row_cal + 1, col, THIN);
+
+        if let Some(date) = NaiveDate::from_ymd_opt(urenstaat.year, urenstaat.month, day) {
+            sheet.text(start_row_cal, col, col, catalog.weekday_short(date.weekday()), CENTERED);
+            sheet.text(start_row_cal + 1, col, col, &day.to_string(), CENTERED);
+
+            for (r, (_, hours)) in lines.iter().enumerate() {
+                let val = hours.get(&day).copied().unwrap_or(0.0);
+                day_totals[day as usize - 1] += val;
+                sheet.text(start_row_hours + r, col, col, &format_hours(val), CENTERED);
+            }
+        }
+    }
+
+    for r in 0..line_count {
+        if let Some((label, _)) = lines.get(r) {
+            sheet.text(start_row_hours + r, 1, 1, label, PLAIN);
+        }
+        sheet.border(start_row_hours + r, 1, start_row_hours + r, 1, THIN);
+        for c in 2..=32 {
+            sheet.border(start_row_hours + r, c, start_row_hours + r, c, THIN);
+        }
+    }
+
+    // --- Totals ---
+    let grand_total: f64 = day_totals.iter().sum();
+    sheet.border(start_row_cal + 1, 33, start_row_cal + 1, 33, MEDIUM);
+    sheet.text(start_row_cal + 1, 33, 33, &labels.total, CENTERED_BOLD);
+    for r in 0..line_count {
+        let row_total: f64 = lines.get(r).map(|(_, hours)| hours.values().sum()).unwrap_or(0.0);
+        sheet.border(start_row_hours + r, 33, start_row_hours + r, 33, MEDIUM);
+        sheet.text(start_row_hours + r, 33, 33, &row_total.to_string(), CENTERED_BOLD);
+    }
+
+    sheet.border(total_facturabel_row, 1, total_facturabel_row, 1, MEDIUM);
+    sheet.text(total_facturabel_row, 1, 1, &labels.total_billable, BOLD);
+    for (i, total) in day_totals.iter().enumerate() {
+        let col = i + 2;
+        sheet.border(total_facturabel_row, col, total_facturabel_row, col, MEDIUM);
+        sheet.text(total_facturabel_row, col, col, &total.to_string(), CENTERED_BOLD);
+    }
+    sheet.border(total_facturabel_row, 33, total_facturabel_row, 33, MEDIUM);
+    sheet.text(total_facturabel_row, 33, 33, &grand_total.to_string(), CENTERED_BOLD);
+
+    // Non-billable hours below, outside the billable total
+    if !urenstaat.non_billable.is_empty() {
+        let row = total_facturabel_row + 1;
+        sheet.border(row, 1, row, 1, THIN);
+        sheet.text(row, 1, 1, &labels.non_billable, PLAIN);
+        for day in 1..=31u32 {
+            let col = day as usize + 1;
+            sheet.border(row, col, row, col, THIN);
+            sheet.text(row, col, col, &format_hours(urenstaat.non_billable.get(&day).copied().unwrap_or(0.0)), CENTERED);
+        }
+        sheet.border(row, 33, row, 33, MEDIUM);
+        sheet.text(row, 33, 33, &urenstaat.non_billable.values().sum::<f64>().to_string(), CENTERED_BOLD);
+    }
+
+    // --- Expenses ---
+    // The registered expenses, then empty lines up to 4; excl. VAT and VAT follow from the
+    // amount incl. 21% VAT like the formulas in the workbook
+    if let Some(expense_start_row) = rows.expenses {
+        let expense_start_row = expense_start_row as usize;
+        let exp_header_row = expense_start_row + 1;
+        let lines = urenstaat.expenses.len().max(4);
+        let exp_total_row = exp_header_row + 1 + lines;
+        sheet.text(expense_start_row, 1, 22, &labels.expenses, BOLD);
+
+        let expense_columns = [
+            (1, 2, &labels.expense_date, BOLD),
+            (3, 22, &labels.expense_description, BOLD),
+            (23, 26, &labels.expense_excl_vat, AMOUNT_BOLD),
+            (27, 29, &labels.expense_vat, AMOUNT_BOLD),
+            (30, 33, &labels.expense_incl_vat, AMOUNT_BOLD),
+        ];
+        for (c1, c2, label, style) in expense_columns {
+            sheet.border(exp_header_row, c1, exp_header_row, c2, MEDIUM);
+            sheet.text(exp_header_row, c1, c2, label, style);
+        }
+
+        let split = |incl: f64| [incl / 121.0 * 100.0, incl / 121.0 * 21.0, incl];
+        for i in 0..lines {
+            let row = exp_header_row + 1 + i;
+            let expense = urenstaat.expenses.get(i);
+            let date = expense.map(|e| e.date.format("%d-%m-%Y").to_string()).unwrap_or_default();
+            let amounts = split(expense.map(|e| e.amount).unwrap_or(0.0));
+            for (c1, c2, value, style) in [
+                (1, 2, date.as_str(), PLAIN),
+                (3, 22, expense.map(|e| e.description.as_str()).unwrap_or_default(), PLAIN),
+                (23, 26, &format_euro(amounts[0]), AMOUNT),
+                (27, 29, &format_euro(amounts[1]), AMOUNT),
+                (30, 33, &format_euro(amounts[2]), AMOUNT),
+            ] {
+                sheet.border(row, c1, row, c2, THIN);
+                sheet.text(row, c1, c2, value, style);
+            }
+        }
+
+        let totals = split(urenstaat.expenses.iter().map(|e| e.amount).sum());
+        sheet.text(exp_total_row, 3, 22, &labels.expense_total, PLAIN);
+        for ((c1, c2), amount) in [(23, 26), (27, 29), (30, 33)].into_iter().zip(totals) {
+            sheet.border(exp_total_row, c1, exp_total_row, c2, THIN);
+            sheet.text(exp_total_row, c1, c2, &format_euro(amount), AMOUNT);
+        }
+    }
+
+    // --- Mileage ---
+    if let Some(mileage_start_row) = rows.mileage {
+        let mileage_start_row = mileage_start_row as usize;
+        let header_row = mileage_start_row + 1;
+        let total_row = header_row + 1 + urenstaat.trips.len();
+        sheet.text(mileage_start_row, 1, 22, &labels.mileage, BOLD);
+
+        let mileage_columns = [
+            (1, 2, &labels.expense_date, BOLD),
+            (3, 10, &labels.mileage_from, BOLD),
+            (11, 18, &labels.mileage_to, BOLD),
+            (19, 22, &labels.mileage_mode, BOLD),
+            (23, 26, &labels.mileage_km, AMOUNT_BOLD),
+            (27, 29, &labels.mileage_rate, AMOUNT_BOLD),
+            (30, 33, &labels.mileage_amount, AMOUNT_BOLD),
+        ];
+        for (c1, c2, label, style) in mileage_columns {
+            sheet.border(header_row, c1, header_row, c2, MEDIUM);
+            sheet.text(header_row, c1, c2, label, style);
+        }
+        for (i, trip) in urenstaat.trips.iter().enumerate() {
+            let row = header_row + 1 + i;
+            let date = trip.date.format("%d-%m-%Y").to_string();
+            let km = format!("{:.1}", trip.km).replace('.', ",");
+            for (c1, c2, value, style) in [
+                (1, 2, date.as_str(), PLAIN),
+                (3, 10, trip.origin.as_str(), PLAIN),
+                (11, 18, trip.destination.as_str(), PLAIN),
+                (19, 22, trip.mode.label(catalog), PLAIN),
+                (23, 26, km.as_str(), AMOUNT),
+                (27, 29, &format_euro(trip.rate), AMOUNT),
+                (30, 33, &format_euro(trip.allowance()), AMOUNT),
+            ] {
+                sheet.border(row, c1, row, c2, THIN);
+                sheet.text(row, c1, c2, value, style);
+            }
+        }
+
+        let km: f64 = urenstaat.trips.iter().map(|t| t.km).sum();
+        let allowance: f64 = urenstaat.trips.iter().map(|t| t.allowance()).sum();
+        sheet.text(total_row, 3, 22, &labels.expense_total, PLAIN);
+        sheet.border(total_row, 23, total_row, 26, THIN);
+        sheet.text(total_row, 23, 26, &format!("{:.1}", km).replace('.', ","), AMOUNT);
+        sheet.border(total_row, 30, total_row, 33, THIN);
+        sheet.text(total_row, 30, 33, &format_euro(allowance), AMOUNT);
+    }
+
+    // --- Signatures ---
+    if let Some(sign_row) = rows.signatures {
+        let sign_row = sign_row as usize;
+        // An approved month carries the reviewer's sign-off on the client's side
+        let approval = urenstaat.approval.as_ref();
+        let client_date = approval.map(|a| a.date.format("%d-%m-%Y").to_string()).unwrap_or(fill_date.clone());
+        for (col, label, name, date) in [
+            (1, &labels.sign_client, urenstaat.project.as_str(), &client_date),
+            (23, &labels.sign_employee, urenstaat.employee.name.as_str(), &fill_date),
+        ] {
+            sheet.text(sign_row, col, col + 9, label, BOLD);
+            sheet.text(sign_row + 1, col, col + 9, name, PLAIN);
+            sheet.text(sign_row + 2, col, col + 9, &labels.sign_date, BOLD);
+            sheet.text(sign_row + 3, col, col + 9, date, PLAIN);
+        }
+
+        sheet.text(sign_row + 4, 1, 9, &labels.signature_client, BOLD);
+        sheet.text(sign_row + 4, 23, 32, &labels.signature_employee, BOLD);
+        sheet.border(sign_row + 5, 1, sign_row + 5, 9, MEDIUM);
+        sheet.border(sign_row + 5, 23, sign_row + 5, 32, MEDIUM);
+        if let Some(a) = approval {
+            sheet.text(sign_row + 5, 1, 9, &Catalog::fill(&labels.approved_by, &[&a.reviewer, &client_date]), BOLD);
+        }
+        let signature = urenstaat.employee.signature(layout);
+        if !signature.is_empty() {
+            sheet.image(signature, sign_row + 5, 23, 300.0, 200.0)?;
+        }
+    }
+
+    doc.save(&mut BufWriter::new(File::create(filename)?))?;
+    Ok(())
+}
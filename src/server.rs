@@ -0,0 +1,315 @@
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, env, error::Error, fmt};
+use tiny_http::{Header, Method, Request, Response, Server};
+use timesheet_core::{day_records, month_bounds, month_projects, parse_week, EntryFilter, Repository, Template};
+
+use crate::{
+    audit::{ChangeSet, ChangeSource},
+    backup, billing, categories, employees::Employee, export_month,
+    locale::{Catalog, Lang},
+    lock, ExportOptions,
+    validate::Rejected,
+};
+
+// --- REST API ---
+// `serve` answers JSON requests on the same timesheet.db. Requests are handled one at a
+// time, and writes go through a ChangeSet (source "api"), so locks, validation, the change
+// log and undo apply as in `log`.
+//
+//   GET    /api/templates                   PUT/DELETE /api/templates/{project}
+//   GET    /api/weeks/{YYYY-W##}            PUT/DELETE /api/weeks/{YYYY-W##}/{project}[/{category}]
+//   GET    /api/months/{year}/{month}       POST       /api/exports
+
+// An error with its HTTP status; other errors become 500, `Rejected` becomes 422
+#[derive(Debug)]
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for HttpError {}
+
+fn http_error(status: u16, message: impl Into<String>) -> Box<dyn Error> {
+    Box::new(HttpError { status, message: message.into() })
+}
+
+#[derive(Deserialize)]
+struct HoursBody {
+    hours: [f64; 7], // Monday first
+    #[serde(default)]
+    category: String, // of a template
+    billable: Option<bool>, // of a week row; left as it is when missing
+}
+
+#[derive(Deserialize)]
+struct ExportBody {
+    year: i32,
+    month: u32,
+    project: Option<String>, // all projects in one workbook when missing
+    #[serde(default)]
+    pdf: bool,
+    layout: Option<String>,
+    #[serde(default)]
+    sign: bool,
+    #[serde(default)]
+    zip: bool,
+}
+
+fn parse_body<T: for<'de> Deserialize<'de>>(body: &str) -> Result<T, Box<dyn Error>> {
+    serde_json::from_str(body).map_err(|e| http_error(400, format!("Invalid request body: {}", e)))
+}
+
+// Project names in the path are percent-encoded ("Acme%20BV")
+fn decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], segment.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => { out.push(byte); i += 3; }
+            (byte, _) => { out.push(byte); i += 1; }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn checked_week(week: &str) -> Result<(), Box<dyn Error>> {
+    match parse_week(week) {
+        Some(_) => Ok(()),
+        None => Err(http_error(400, format!("Invalid week '{}', expected YYYY-W##", week))),
+    }
+}
+
+// --- Templates ---
+fn get_templates(repo: &dyn Repository) -> Result<Value, Box<dyn Error>> {
+    let templates: Vec<Value> = repo
+        .templates()?
+        .into_iter()
+        .map(|t| json!({ "project": t.project, "category": t.category, "hours": t.hours, "total": t.hours.iter().sum::<f64>() }))
+        .collect();
+    Ok(json!(templates))
+}
+
+fn put_template(repo: &dyn Repository, tr: &Catalog, project: &str, body: &str) -> Result<Value, Box<dyn Error>> {
+    let body = parse_body::<HoursBody>(body)?;
+    let template = Template { project: project.to_string(), category: categories::parse(tr, &body.category), hours: body.hours };
+    if !repo.update_template(&template)? {
+        repo.insert_template(&template)?;
+    }
+    Ok(json!({ "project": project, "category": template.category, "hours": template.hours }))
+}
+
+fn delete_template(conn: &Connection, repo: &dyn Repository, project: &str) -> Result<Value, Box<dyn Error>> {
+    backup::auto(conn, "delete-template")?;
+    if !repo.delete_template(project)? {
+        return Err(http_error(404, format!("No template for '{}'", project)));
+    }
+    Ok(json!({ "deleted": project }))
+}
+
+// --- Weekly Entries ---
+fn get_week(repo: &dyn Repository, tr: &Catalog, week: &str) -> Result<Value, Box<dyn Error>> {
+    checked_week(week)?;
+    let rows = repo.entries(Some(week), None)?;
+    let billing = billing::load(repo, tr)?;
+
+    let mut day_totals = [0.0; 7];
+    for row in &rows {
+        for (total, h) in day_totals.iter_mut().zip(row.hours) { *total += h; }
+    }
+    let entries: Vec<Value> = rows
+        .iter()
+        .map(|e| json!({ "project": e.project, "category": e.category, "hours": e.hours, "total": e.total(), "billable": billing.is_billable(e) }))
+        .collect();
+    Ok(json!({ "week": week, "entries": entries, "day_totals": day_totals, "total": day_totals.iter().sum::<f64>() }))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn put_week(conn: &Connection, repo: &dyn Repository, tr: &Catalog, week: &str, project: &str, category: &str, body: &str) -> Result<Value, Box<dyn Error>> {
+    checked_week(week)?;
+    let HoursBody { hours, billable, .. } = parse_body::<HoursBody>(body)?;
+    let category = categories::parse(tr, category);
+    let row = repo.entry(week, project, &category)?;
+    let current = row.as_ref().map(|e| e.hours);
+
+    let mut changed: Vec<usize> = (0..7).filter(|&d| current.map(|c| c[d]).unwrap_or(0.0) != hours[d]).collect();
+    // A new billable flag changes every day with hours on the sheet
+    let flag_changed = billable.is_some() && billable != row.as_ref().and_then(|e| e.billable);
+    if flag_changed {
+        changed = (0..7).filter(|&d| hours[d] != 0.0 || current.is_some_and(|c| c[d] != 0.0)).collect();
+    }
+    if let Some(date) = lock::locked_day(conn, project, week, &changed)? {
+        return Err(http_error(409, Catalog::fill(tr.day_locked, &[&project, &date, &date.month(), &date.year()])));
+    }
+
+    let changes = ChangeSet::begin(conn, repo, ChangeSource::Api)?;
+    match current {
+        Some(_) => {
+            for day in changed { changes.set_day(week, project, &category, day, hours[day])?; }
+        }
+        None => changes.insert_row(week, project, &category, hours, billable)?,
+    }
+    if flag_changed && current.is_some() {
+        changes.set_billable(week, project, &category, billable)?;
+    }
+    let committed = changes.commit()?;
+    let warnings: Vec<String> = committed.warnings.iter().map(|v| v.message(tr)).collect();
+    Ok(json!({ "week": week, "project": project, "category": category, "hours": hours, "warnings": warnings }))
+}
+
+fn delete_week(conn: &Connection, repo: &dyn Repository, tr: &Catalog, week: &str, project: &str, category: &str) -> Result<Value, Box<dyn Error>> {
+    checked_week(week)?;
+    let category = categories::parse(tr, category);
+    let Some(current) = repo.entry(week, project, &category)?.map(|e| e.hours) else {
+        return Err(http_error(404, format!("No entry for '{}' in {}", project, week)));
+    };
+    let days: Vec<usize> = (0..7).filter(|&d| current[d] != 0.0).collect();
+    if let Some(date) = lock::locked_day(conn, project, week, &days)? {
+        return Err(http_error(409, Catalog::fill(tr.day_locked, &[&project, &date, &date.month(), &date.year()])));
+    }
+
+    backup::auto(conn, "remove-project")?;
+    let changes = ChangeSet::begin(conn, repo, ChangeSource::Api)?;
+    changes.delete_row(week, project, &category)?;
+    changes.commit()?;
+    Ok(json!({ "deleted": project, "category": category, "week": week }))
+}
+
+// --- Month Aggregation ---
+fn get_month(repo: &dyn Repository, tr: &Catalog, year: &str, month: &str) -> Result<Value, Box<dyn Error>> {
+    let year: i32 = year.parse().map_err(|_| http_error(400, format!("Invalid year '{}'", year)))?;
+    let month: u32 = month.parse().map_err(|_| http_error(400, format!("Invalid month '{}'", month)))?;
+    let (first, last) = month_bounds(year, month).map_err(|_| http_error(400, format!("Invalid month {}", month)))?;
+
+    let mut projects: BTreeMap<String, BTreeMap<NaiveDate, f64>> = BTreeMap::new();
+    let mut categories: BTreeMap<String, f64> = BTreeMap::new(); // "" for uncategorised hours
+    for record in day_records(repo, Some(first), Some(last), None)? {
+        *projects.entry(record.project).or_default().entry(record.date).or_insert(0.0) += record.hours;
+        *categories.entry(record.category).or_insert(0.0) += record.hours;
+    }
+    let billing = billing::load(repo, tr)?;
+    let mut billable: BTreeMap<String, f64> = BTreeMap::new();
+    for record in day_records(&EntryFilter::billable(repo, &billing, true), Some(first), Some(last), None)? {
+        *billable.entry(record.project).or_insert(0.0) += record.hours;
+    }
+
+    let mut total = 0.0;
+    let projects: Vec<Value> = projects
+        .into_iter()
+        .map(|(project, days)| {
+            let project_total: f64 = days.values().sum();
+            total += project_total;
+            let project_billable = billable.get(&project).copied().unwrap_or(0.0);
+            let days: BTreeMap<String, f64> = days.into_iter().map(|(d, h)| (d.to_string(), h)).collect();
+            json!({ "project": project, "days": days, "total": project_total, "billable": project_billable })
+        })
+        .collect();
+    let billable_total: f64 = billable.values().sum();
+    Ok(json!({
+        "year": year, "month": month, "projects": projects, "categories": categories, "total": total,
+        "billable": billable_total, "non_billable": total - billable_total,
+    }))
+}
+
+// --- Export ---
+fn post_export(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, lang: Option<Lang>, body: &str) -> Result<Value, Box<dyn Error>> {
+    let body = parse_body::<ExportBody>(body)?;
+    month_bounds(body.year, body.month).map_err(|_| http_error(400, format!("Invalid month {}", body.month)))?;
+    // The project ends up in the file name, so only projects with hours in the month are exported
+    if let Some(project) = &body.project
+        && !month_projects(repo, body.year, body.month)?.contains(project)
+    {
+        return Err(http_error(404, format!("No hours for project {} in {}/{}", project, body.month, body.year)));
+    }
+    // Only the layout from EXPORT_LAYOUT can be named; other files on the machine are not read
+    if let Some(layout) = &body.layout
+        && env::var("EXPORT_LAYOUT").ok().as_ref() != Some(layout)
+    {
+        return Err(http_error(400, format!("Layout {} is not the configured EXPORT_LAYOUT", layout)));
+    }
+    let opts = ExportOptions { pdf: body.pdf, all: body.project.is_none(), layout: body.layout, sign: body.sign, zip: body.zip };
+    let files = export_month(conn, repo, employee, tr, lang, &opts, body.project.as_deref(), body.year, body.month)?;
+    if files.is_empty() {
+        return Err(http_error(404, Catalog::fill(tr.no_data, &[&body.month, &body.year])));
+    }
+    Ok(json!({ "files": files }))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn route(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, lang: Option<Lang>, method: &Method, path: &str, body: &str) -> Result<Value, Box<dyn Error>> {
+    let segments: Vec<String> = path.trim_matches('/').split('/').map(decode).collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    match (method, segments.as_slice()) {
+        (Method::Get, ["api", "templates"]) => get_templates(repo),
+        (Method::Put, ["api", "templates", project]) => put_template(repo, tr, project, body),
+        (Method::Delete, ["api", "templates", project]) => delete_template(conn, repo, project),
+        (Method::Get, ["api", "weeks", week]) => get_week(repo, tr, week),
+        (Method::Put, ["api", "weeks", week, project]) => put_week(conn, repo, tr, week, project, "", body),
+        (Method::Put, ["api", "weeks", week, project, category]) => put_week(conn, repo, tr, week, project, category, body),
+        (Method::Delete, ["api", "weeks", week, project]) => delete_week(conn, repo, tr, week, project, ""),
+        (Method::Delete, ["api", "weeks", week, project, category]) => delete_week(conn, repo, tr, week, project, category),
+        (Method::Get, ["api", "months", year, month]) => get_month(repo, tr, year, month),
+        (Method::Post, ["api", "exports"]) => post_export(conn, repo, employee, tr, lang, body),
+        _ => Err(http_error(404, format!("No route for {} {}", method, path))),
+    }
+}
+
+fn respond(mut request: Request, conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, lang: Option<Lang>) -> Result<(), Box<dyn Error>> {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let path = url.split('?').next().unwrap_or("").to_string();
+    let mut body = String::new();
+    request.as_reader().read_to_string(&mut body)?;
+
+    // A web page can send a plain-text POST to localhost without a preflight; a JSON content
+    // type cannot be sent that way
+    let json_body = request.headers().iter().any(|h| {
+        h.field.equiv("Content-Type") && h.value.as_str().split(';').next().unwrap_or("").trim().eq_ignore_ascii_case("application/json")
+    });
+    let result = if method == Method::Post && !json_body {
+        Err(http_error(415, "Content-Type must be application/json"))
+    } else {
+        route(conn, repo, employee, tr, lang, &method, &path, &body)
+    };
+
+    let (status, value) = match result {
+        Ok(value) => (200, value),
+        Err(e) => {
+            if let Some(Rejected(errors)) = e.downcast_ref::<Rejected>() {
+                let messages: Vec<String> = errors.iter().map(|v| v.message(tr)).collect();
+                (422, json!({ "error": "rejected", "violations": messages }))
+            } else if let Some(http) = e.downcast_ref::<HttpError>() {
+                (http.status, json!({ "error": http.message }))
+            } else {
+                (500, json!({ "error": e.to_string() }))
+            }
+        }
+    };
+    println!("{} {} -> {}", method, url, status);
+
+    let header = Header::from_bytes("Content-Type", "application/json").map_err(|_| "Invalid header")?;
+    request.respond(Response::from_string(value.to_string()).with_status_code(status).with_header(header))?;
+    Ok(())
+}
+
+// --- Function: Serve the REST API ---
+pub fn handle_serve(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, lang: Option<Lang>, bind: &str) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(bind).map_err(|e| format!("Cannot listen on {}: {}", bind, e))?;
+    println!("{}", Catalog::fill(tr.serving, &[&bind]));
+    for request in server.incoming_requests() {
+        // A failing response (e.g. a closed connection) must not stop the server
+        if let Err(e) = respond(request, conn, repo, employee, tr, lang) {
+            println!("{}", e);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use chrono::Local;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{employees::Employee, locale::Catalog};
+
+// --- Signed Exports ---
+// `export --sign` writes a manifest next to each exported file (<file>.sig) with the file's
+// SHA-256, the period, the employee and the total hours, signed with the employee's Ed25519
+// key. The keys are kept in KEY_DIR (default "keys"): timesheet.key for your own sheet and
+// timesheet_<code>.key for an employee, each with the public key next to it (.pub).
+// `verify` checks a file against its manifest, signed by a trusted public key: the one given
+// with --key, or one of the .pub files in KEY_DIR.
+
+const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Manifest {
+    version: u32,
+    file: String, // file name without the folder, so the pair can be moved together
+    sha256: String,
+    period: String, // "2026-09" or "2026-W37"
+    project: Option<String>, // None for a workbook or sheet with several projects
+    employee: String,
+    total_hours: f64,
+    signed_at: String,
+}
+
+// The manifest as signed, with the key that signed it (hex)
+#[derive(Debug, Serialize, Deserialize)]
+struct SignedManifest {
+    manifest: Manifest,
+    public_key: String,
+    signature: String,
+}
+
+// What a signature covers: the manifest as compact JSON, fields in declaration order
+fn signed_bytes(manifest: &Manifest) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(serde_json::to_vec(manifest)?)
+}
+
+fn key_dir() -> PathBuf {
+    PathBuf::from(env::var("KEY_DIR").ok().filter(|d| !d.trim().is_empty()).unwrap_or("keys".to_string()))
+}
+
+fn key_path(employee: &Employee) -> PathBuf {
+    let name = match &employee.code {
+        Some(code) => format!("timesheet_{}.key", code),
+        None => "timesheet.key".to_string(),
+    };
+    key_dir().join(name)
+}
+
+// Short, readable form of a public key to compare by phone or mail
+fn fingerprint(key: &VerifyingKey) -> String {
+    let hash = hex::encode(Sha256::digest(key.as_bytes()));
+    hash.as_bytes()[..16].chunks(4).map(|c| String::from_utf8_lossy(c).to_string()).collect::<Vec<_>>().join(":")
+}
+
+fn parse_public_key(text: &str) -> Result<VerifyingKey, Box<dyn Error>> {
+    let bytes: [u8; 32] = hex::decode(text.trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or("A public key must be 64 hexadecimal characters")?;
+    Ok(VerifyingKey::from_bytes(&bytes)?)
+}
+
+// The employee's signing key; a new one is generated (and its public key saved) on first use
+fn signing_key(tr: &Catalog, employee: &Employee) -> Result<SigningKey, Box<dyn Error>> {
+    let path = key_path(employee);
+    if path.exists() {
+        let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let bytes: [u8; 32] = hex::decode(text.trim())
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| format!("{}: not a signing key", path.display()))?;
+        return Ok(SigningKey::from_bytes(&bytes));
+    }
+
+    let key = SigningKey::generate(&mut OsRng);
+    fs::create_dir_all(key_dir())?;
+    // Only the owner may read the private key, from the moment the file exists
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    file.write_all(hex::encode(key.to_bytes()).as_bytes()).map_err(|e| format!("{}: {}", path.display(), e))?;
+    fs::write(path.with_extension("pub"), hex::encode(key.verifying_key().as_bytes()))?;
+    println!("{}", Catalog::fill(tr.key_generated, &[&path.display(), &fingerprint(&key.verifying_key())]));
+    Ok(key)
+}
+
+fn file_sha256(path: &Path) -> Result<String, Box<dyn Error>> {
+    let bytes = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+fn signature_path(file: &Path) -> PathBuf {
+    let mut name = file.as_os_str().to_os_string();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+// --- Function: Sign an exported file ---
+// Writes <file>.sig next to the file
+pub fn sign_file(tr: &Catalog, employee: &Employee, file: &str, period: &str, project: Option<&str>, total_hours: f64) -> Result<(), Box<dyn Error>> {
+    let key = signing_key(tr, employee)?;
+    let path = Path::new(file);
+    let manifest = Manifest {
+        version: MANIFEST_VERSION,
+        file: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or(file.to_string()),
+        sha256: file_sha256(path)?,
+        period: period.to_string(),
+        project: project.map(str::to_string),
+        employee: employee.name.clone(),
+        total_hours,
+        signed_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+    let signature = key.sign(&signed_bytes(&manifest)?);
+    let signed = SignedManifest {
+        manifest,
+        public_key: hex::encode(key.verifying_key().as_bytes()),
+        signature: hex::encode(signature.to_bytes()),
+    };
+
+    let sig_path = signature_path(path);
+    fs::write(&sig_path, serde_json::to_string_pretty(&signed)?).map_err(|e| format!("{}: {}", sig_path.display(), e))?;
+    println!("{}", Catalog::fill(tr.file_signed, &[&sig_path.display()]));
+    Ok(())
+}
+
+// --- Function: Show the public key ---
+pub fn handle_key(tr: &Catalog, employee: &Employee) -> Result<(), Box<dyn Error>> {
+    let key = signing_key(tr, employee)?.verifying_key();
+    println!("{}", Catalog::fill(tr.public_key, &[&employee, &hex::encode(key.as_bytes()), &fingerprint(&key)]));
+    Ok(())
+}
+
+// --- Function: Verify a signed export ---
+// Returns false when the file, the manifest or the key does not match
+// The public keys in KEY_DIR: your own, the employees' and those received from others
+fn known_keys() -> Vec<VerifyingKey> {
+    let Ok(entries) = fs::read_dir(key_dir()) else { return Vec::new(); };
+    entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "pub"))
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .filter_map(|text| parse_public_key(&text).ok())
+        .collect()
+}
+
+pub fn handle_verify(tr: &Catalog, file: &str, signature: Option<String>, key: Option<String>) -> Result<bool, Box<dyn Error>> {
+    let path = Path::new(file);
+    let sig_path = signature.map(PathBuf::from).unwrap_or_else(|| signature_path(path));
+    let text = fs::read_to_string(&sig_path).map_err(|e| format!("{}: {}", sig_path.display(), e))?;
+    let signed: SignedManifest = serde_json::from_str(&text).map_err(|e| format!("{}: {}", sig_path.display(), e))?;
+    let manifest = &signed.manifest;
+
+    let public_key = parse_public_key(&signed.public_key)?;
+    let bytes: [u8; 64] = hex::decode(&signed.signature)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| format!("{}: invalid signature", sig_path.display()))?;
+    if public_key.verify(&signed_bytes(manifest)?, &Signature::from_bytes(&bytes)).is_err() {
+        println!("{}", Catalog::fill(tr.signature_invalid, &[&sig_path.display()]));
+        return Ok(false);
+    }
+
+    // A key given by the client (hex, or a .pub file) must be the one that signed. Without
+    // it the key must be known in KEY_DIR: anyone can sign an altered file with a key of
+    // their own and embed that in the manifest.
+    if let Some(trusted) = key {
+        let trusted = match fs::read_to_string(&trusted) {
+            Ok(text) => text,
+            Err(_) => trusted,
+        };
+        if parse_public_key(&trusted)? != public_key {
+            println!("{}", Catalog::fill(tr.signature_other_key, &[&fingerprint(&public_key)]));
+            return Ok(false);
+        }
+    } else if !known_keys().contains(&public_key) {
+        println!("{}", Catalog::fill(tr.signature_untrusted, &[&fingerprint(&public_key)]));
+        return Ok(false);
+    }
+
+    let actual = file_sha256(path)?;
+    if actual != manifest.sha256 {
+        println!("{}", Catalog::fill(tr.file_altered, &[&file, &manifest.signed_at]));
+        return Ok(false);
+    }
+
+    let project = manifest.project.as_deref().unwrap_or("-");
+    println!(
+        "{}",
+        Catalog::fill(
+            tr.signature_valid,
+            &[&file, &manifest.employee, &manifest.signed_at, &project, &manifest.period, &manifest.total_hours, &fingerprint(&public_key)],
+        )
+    );
+    Ok(true)
+}
@@ -0,0 +1,407 @@
+use chrono::{Datelike, Local};
+use inquire::{CustomType, Select};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+use timesheet_core::{sqlite::DAY_COLUMNS, week_date, Repository};
+
+use crate::{
+    audit::{code_flag, flag_action, flag_code, flag_label, ChangeSet, ChangeSource},
+    categories,
+    employees::Employee,
+    locale::Catalog,
+    lock,
+};
+
+// --- Sync ---
+// Devices exchange their change log through a shared folder (Syncthing, a network share):
+// every device writes its own changes to SYNC_DIR/<device>/ and reads those of the others.
+// A change is applied when the day still has the value it was changed from; when both
+// devices changed the same day differently, the user resolves the conflict. Changes applied
+// by sync are not written out again, but resolutions are, so the other device follows.
+// Employees sync through their own subfolder, SYNC_DIR/<code>/.
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SyncFile {
+    device: String,
+    changes: Vec<SyncChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncChange { // also kept as JSON in sync_pending
+    id: i64, // id in the change log of the device that made it
+    changed_at: String,
+    source: String,
+    action: String,
+    week: String,
+    project: String,
+    #[serde(default)]
+    category: String, // missing in files of older versions: uncategorised
+    day: String,
+    old: f64, // the flags as codes for billable changes, see audit::flag_code
+    new: f64,
+    #[serde(default)]
+    billable: Option<bool>, // the row's own flag of added rows
+}
+
+pub fn init_sync(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    // Own device name and the last change written to the shared folder
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_state (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // Last change applied per other device
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_peers (
+            device TEXT PRIMARY KEY,
+            last_id INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    // Changes of other devices to locked days, retried on every sync until they are unlocked
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_pending (
+            device TEXT NOT NULL,
+            id INTEGER NOT NULL,
+            change TEXT NOT NULL,
+            PRIMARY KEY (device, id)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn state(conn: &Connection, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+    Ok(conn.query_row("SELECT value FROM sync_state WHERE key = ?1", params![key], |row| row.get(0)).optional()?)
+}
+
+fn set_state(conn: &Connection, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    conn.execute(
+        "INSERT INTO sync_state (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
+// SYNC_DEVICE from .env, or a name generated once and kept in the database
+fn device_name(conn: &Connection) -> Result<String, Box<dyn Error>> {
+    if let Ok(name) = env::var("SYNC_DEVICE")
+        && !name.trim().is_empty() {
+        return Ok(name.trim().to_string());
+    }
+    if let Some(name) = state(conn, "device")? {
+        return Ok(name);
+    }
+    let name = format!("device-{:x}", Local::now().timestamp_nanos_opt().unwrap_or_default());
+    set_state(conn, "device", &name)?;
+    Ok(name)
+}
+
+// Writes this device's changes since the last sync; returns how many
+fn export_changes(conn: &Connection, dir: &Path, device: &str) -> Result<usize, Box<dyn Error>> {
+    let last: i64 = state(conn, "last_exported")?.and_then(|v| v.parse().ok()).unwrap_or(0);
+    // Changes applied from other devices are theirs, except for conflict resolutions
+    let mut stmt = conn.prepare(
+        "SELECT id, changed_at, source, action, week, project, day, old_value, new_value, category, billable FROM changes
+         WHERE id > ?1 AND (source != 'sync' OR action IN ('resolve', 'resolve_billable')) ORDER BY id",
+    )?;
+    let changes = stmt
+        .query_map(params![last], |row| {
+            Ok(SyncChange {
+                id: row.get(0)?, changed_at: row.get(1)?, source: row.get(2)?, action: row.get(3)?,
+                week: row.get(4)?, project: row.get(5)?, day: row.get(6)?, old: row.get(7)?, new: row.get(8)?,
+                category: row.get(9)?, billable: row.get(10)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    let newest: Option<i64> = conn.query_row("SELECT MAX(id) FROM changes", [], |row| row.get(0))?;
+
+    if let Some(change) = changes.last() {
+        let device_dir = dir.join(device);
+        fs::create_dir_all(&device_dir).map_err(|e| format!("{}: {}", device_dir.display(), e))?;
+        let path = device_dir.join(format!("changes_{:010}.json", change.id));
+        let file = SyncFile { device: device.to_string(), changes: changes.clone() };
+        fs::write(&path, serde_json::to_string_pretty(&file)?).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    if let Some(newest) = newest {
+        set_state(conn, "last_exported", &newest.to_string())?;
+    }
+    Ok(changes.len())
+}
+
+// A change file written by export_changes
+fn is_change_file(path: &Path) -> bool {
+    path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("changes_") && n.ends_with(".json"))
+}
+
+// A folder of another device holds its change files; the folders of employees (`--employee`)
+// or receipts sharing the sync folder do not
+fn is_device_dir(path: &Path) -> bool {
+    path.is_dir()
+        && fs::read_dir(path).is_ok_and(|entries| entries.filter_map(Result::ok).any(|e| is_change_file(&e.path())))
+}
+
+// Changes of another device that were not applied yet, oldest first
+fn load_peer(conn: &Connection, device_dir: &Path) -> Result<(String, Vec<SyncChange>), Box<dyn Error>> {
+    let device = device_dir.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string();
+    let last: i64 = conn
+        .query_row("SELECT last_id FROM sync_peers WHERE device = ?1", params![device], |row| row.get(0))
+        .optional()?
+        .unwrap_or(0);
+
+    // Changes still waiting for a locked period go first, in their own order
+    let mut stmt = conn.prepare("SELECT change FROM sync_pending WHERE device = ?1 ORDER BY id")?;
+    let mut changes = Vec::new();
+    for text in stmt.query_map(params![device], |row| row.get::<_, String>(0))? {
+        changes.push(serde_json::from_str::<SyncChange>(&text?)?);
+    }
+    for entry in fs::read_dir(device_dir)? {
+        let path = entry?.path();
+        if !is_change_file(&path) { continue; }
+        let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let file: SyncFile = serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        changes.extend(file.changes.into_iter().filter(|c| c.id > last));
+    }
+    changes.sort_by_key(|c| c.id);
+    changes.dedup_by_key(|c| c.id);
+    Ok((device, changes))
+}
+
+// Unlike the tables, a conflict shows empty days as 0
+fn show(hours: f64) -> String {
+    hours.to_string()
+}
+
+fn prompt_conflict(tr: &Catalog, device: &str, change: &SyncChange, mine: f64) -> Result<f64, Box<dyn Error>> {
+    let day = DAY_COLUMNS.iter().position(|d| *d == change.day).unwrap_or(0);
+    let date = week_date(&change.week, day).map(|d| d.to_string()).unwrap_or(change.week.clone());
+    println!(
+        "{}",
+        Catalog::fill(tr.sync_conflict, &[&categories::row_label(tr, &change.project, &change.category), &date, &show(mine), &show(change.new), &device, &show(change.old)])
+    );
+
+    let options = vec![
+        Catalog::fill(tr.keep_mine, &[&show(mine)]),
+        Catalog::fill(tr.take_theirs, &[&device, &show(change.new)]),
+        tr.enter_other.to_string(),
+    ];
+    Ok(match Select::new(tr.action, options).raw_prompt()?.index {
+        0 => mine,
+        1 => change.new,
+        _ => CustomType::<f64>::new(&Catalog::fill(tr.hours_for, &[&date])).with_default(mine).prompt()?,
+    })
+}
+
+// Same as prompt_conflict, for a row's billable flag
+fn prompt_flag_conflict(tr: &Catalog, device: &str, change: &SyncChange, mine: f64) -> Result<f64, Box<dyn Error>> {
+    println!(
+        "{}",
+        Catalog::fill(tr.sync_conflict, &[
+            &categories::row_label(tr, &change.project, &change.category), &change.week,
+            &flag_label(tr, mine), &flag_label(tr, change.new), &device, &flag_label(tr, change.old),
+        ])
+    );
+    let options = vec![
+        Catalog::fill(tr.keep_mine, &[&flag_label(tr, mine)]),
+        Catalog::fill(tr.take_theirs, &[&device, &flag_label(tr, change.new)]),
+    ];
+    Ok(if Select::new(tr.action, options).raw_prompt()?.index == 0 { mine } else { change.new })
+}
+
+// Counts of one sync run
+#[derive(Default)]
+struct Applied {
+    applied: usize,
+    present: usize,
+    conflicts: usize,
+    locked: usize,
+}
+
+fn apply_peer(conn: &Connection, repo: &dyn Repository, tr: &Catalog, device: &str, changes: &[SyncChange]) -> Result<Applied, Box<dyn Error>> {
+    let mut counts = Applied::default();
+    let set = ChangeSet::begin(conn, repo, ChangeSource::Sync)?;
+    // Changes to locked days wait in sync_pending; the ones still locked are added again
+    conn.execute("DELETE FROM sync_pending WHERE device = ?1", params![device])?;
+    let wait = |change: &SyncChange| -> Result<(), Box<dyn Error>> {
+        conn.execute(
+            "INSERT INTO sync_pending (device, id, change) VALUES (?1, ?2, ?3)",
+            params![device, change.id, serde_json::to_string(change)?],
+        )?;
+        Ok(())
+    };
+    for (i, change) in changes.iter().enumerate() {
+        let day = DAY_COLUMNS
+            .iter()
+            .position(|d| *d == change.day)
+            .ok_or_else(|| format!("{}: unknown day '{}'", device, change.day))?;
+        if week_date(&change.week, day).is_none() {
+            return Err(format!("{}: invalid week '{}'", device, change.week).into());
+        }
+
+        let row = repo.entry(&change.week, &change.project, &change.category)?;
+        let same_row = |c: &SyncChange| c.week == change.week && c.project == change.project && c.category == change.category;
+
+        // A row's billable flag; it touches every day of the row with hours
+        if flag_action(&change.action) {
+            let Some(entry) = row else {
+                // Removed here: the flag went with the row
+                counts.present += 1;
+                continue;
+            };
+            let mine = flag_code(entry.billable);
+            if mine == change.new {
+                counts.present += 1;
+                continue;
+            }
+            let days: Vec<usize> = (0..7).filter(|&d| entry.hours[d] != 0.0).collect();
+            if let Some(date) = lock::locked_day(conn, &change.project, &change.week, &days)? {
+                println!("{}", Catalog::fill(tr.day_locked, &[&change.project, &date, &date.month(), &date.year()]));
+                wait(change)?;
+                counts.locked += 1;
+                continue;
+            }
+            if changes[i + 1..].iter().any(|c| c.action == "resolve_billable" && same_row(c) && c.old == mine) {
+                continue;
+            }
+            let value = if mine == change.old {
+                counts.applied += 1;
+                change.new
+            } else {
+                counts.conflicts += 1;
+                let chosen = prompt_flag_conflict(tr, device, change, mine)?;
+                set.resolve_billable(&change.week, &change.project, &change.category, code_flag(change.new), code_flag(chosen))?;
+                chosen
+            };
+            set.set_billable(&change.week, &change.project, &change.category, code_flag(value))?;
+            continue;
+        }
+
+        let mine = row.as_ref().map(|e| e.hours[day]).unwrap_or(0.0);
+        // With the day already right, only the row itself can be missing (an added row
+        // without hours) or left over (a removed row whose days are all empty here)
+        let row_pending = match change.action.as_str() {
+            "add" => row.is_none(),
+            "remove" => row.as_ref().is_some_and(|e| e.hours.iter().all(|h| *h == 0.0)),
+            _ => false,
+        };
+        if mine == change.new && !row_pending {
+            counts.present += 1;
+            continue;
+        }
+        if let Some(date) = lock::locked_day(conn, &change.project, &change.week, &[day])? {
+            println!("{}", Catalog::fill(tr.day_locked, &[&change.project, &date, &date.month(), &date.year()]));
+            wait(change)?;
+            counts.locked += 1;
+            continue;
+        }
+        if mine == change.new {
+            match row {
+                None => set.insert_row(&change.week, &change.project, &change.category, [0.0; 7], change.billable)?,
+                Some(_) => set.delete_row(&change.week, &change.project, &change.category)?,
+            }
+            counts.applied += 1;
+            continue;
+        }
+
+        // The other device already resolved a conflict with our value; its resolution follows
+        if changes[i + 1..].iter().any(|c| c.action == "resolve" && same_row(c) && c.day == change.day && c.old == mine) {
+            continue;
+        }
+
+        let value = if mine == change.old {
+            counts.applied += 1;
+            change.new
+        } else {
+            counts.conflicts += 1;
+            let chosen = prompt_conflict(tr, device, change, mine)?;
+            set.resolve(&change.week, &change.project, &change.category, day, change.new, chosen)?;
+            chosen
+        };
+
+        match row {
+            Some(_) => set.set_day(&change.week, &change.project, &change.category, day, value)?,
+            None => {
+                let mut hours = [0.0; 7];
+                hours[day] = value;
+                let billable = if change.action == "add" { change.billable } else { None };
+                set.insert_row(&change.week, &change.project, &change.category, hours, billable)?;
+            }
+        }
+        // A removed project row disappears here too once all its days are empty
+        if change.action == "remove"
+            && repo.entry(&change.week, &change.project, &change.category)?.is_some_and(|e| e.hours.iter().all(|h| *h == 0.0)) {
+            set.delete_row(&change.week, &change.project, &change.category)?;
+        }
+    }
+
+    // Waiting changes are older than the ones read from the folder, so the last id never goes back
+    if let Some(last) = changes.last() {
+        conn.execute(
+            "INSERT INTO sync_peers (device, last_id) VALUES (?1, ?2)
+             ON CONFLICT(device) DO UPDATE SET last_id = MAX(last_id, excluded.last_id)",
+            params![device, last.id],
+        )?;
+    }
+    set.commit()?;
+    Ok(counts)
+}
+
+// --- Function: Sync with other devices through a shared folder ---
+pub fn handle_sync(conn: &Connection, repo: &dyn Repository, employee: &Employee, tr: &Catalog, dir: Option<String>) -> Result<(), Box<dyn Error>> {
+    let dir = dir
+        .or_else(|| env::var("SYNC_DIR").ok())
+        .filter(|d| !d.trim().is_empty())
+        .map(PathBuf::from)
+        .ok_or(tr.sync_no_dir)?;
+    if !dir.is_dir() {
+        return Err(format!("{}: not a directory", dir.display()).into());
+    }
+    let dir = match &employee.code {
+        Some(code) => {
+            let dir = dir.join(code);
+            fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+            dir
+        }
+        None => dir,
+    };
+    let device = device_name(conn)?;
+
+    // Other devices first, so resolutions of this run are written out right away
+    let mut peers: Vec<PathBuf> = fs::read_dir(&dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|p| p.file_name().is_some_and(|n| n != device.as_str()) && is_device_dir(p))
+        .collect();
+    peers.sort();
+
+    let mut nothing_new = true;
+    for peer in peers {
+        let (peer_device, changes) = load_peer(conn, &peer)?;
+        if changes.is_empty() { continue; }
+        nothing_new = false;
+        let counts = apply_peer(conn, repo, tr, &peer_device, &changes)?;
+        println!(
+            "{}",
+            Catalog::fill(tr.sync_applied, &[&peer_device, &counts.applied, &counts.present, &counts.conflicts, &counts.locked])
+        );
+        if counts.locked > 0 {
+            println!("{}", Catalog::fill(tr.sync_waiting, &[&counts.locked, &peer_device]));
+        }
+    }
+    if nothing_new {
+        println!("{}", tr.sync_nothing_new);
+    }
+
+    let exported = export_changes(conn, &dir, &device)?;
+    println!("{}", Catalog::fill(tr.sync_exported, &[&exported, &dir.join(&device).display()]));
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use chrono::{Datelike, Local, NaiveDate};
+use inquire::{Confirm, CustomType, Select, Text};
+use prettytable::{format, Cell, Row, Table};
+use rusqlite::{params, Connection};
+use std::{collections::BTreeMap, env, error::Error, fmt};
+use timesheet_core::{month_bounds, Repository};
+
+use crate::{choose_project, lock, locale::Catalog};
+
+// --- Travel & Mileage ---
+// Trips for the kilometer declaration: date, project, from/to, kilometers and the means of
+// transport. The allowance per kilometer comes from MILEAGE_RATE in .env (default 0.23) and
+// is stored with each trip, so a new rate does not change trips already declared. Trips live
+// in timesheet.db (or the employee's database), also with plain-text storage.
+
+const DEFAULT_RATE: f64 = 0.23;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportMode {
+    Car,
+    Bike,
+    PublicTransport,
+    Other,
+}
+
+impl TransportMode {
+    const ALL: [TransportMode; 4] = [TransportMode::Car, TransportMode::Bike, TransportMode::PublicTransport, TransportMode::Other];
+
+    fn code(self) -> &'static str {
+        match self {
+            TransportMode::Car => "car",
+            TransportMode::Bike => "bike",
+            TransportMode::PublicTransport => "public_transport",
+            TransportMode::Other => "other",
+        }
+    }
+
+    fn parse(code: &str) -> TransportMode {
+        TransportMode::ALL.into_iter().find(|m| m.code() == code).unwrap_or(TransportMode::Other)
+    }
+
+    pub fn label(self, tr: &Catalog) -> &'static str {
+        match self {
+            TransportMode::Car => tr.mode_car,
+            TransportMode::Bike => tr.mode_bike,
+            TransportMode::PublicTransport => tr.mode_public_transport,
+            TransportMode::Other => tr.mode_other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Trip {
+    id: i64,
+    pub date: NaiveDate,
+    pub project: String,
+    pub origin: String,
+    pub destination: String,
+    pub km: f64,
+    pub mode: TransportMode,
+    pub rate: f64, // allowance per km when the trip was registered
+    label: String, // as shown in the menus
+}
+
+impl Trip {
+    pub fn allowance(&self) -> f64 {
+        self.km * self.rate
+    }
+}
+
+impl fmt::Display for Trip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+pub fn init_travel(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS trips (
+            id INTEGER PRIMARY KEY,
+            date TEXT NOT NULL,
+            project TEXT NOT NULL,
+            origin TEXT NOT NULL,
+            destination TEXT NOT NULL,
+            km REAL NOT NULL,
+            mode TEXT NOT NULL,
+            rate REAL NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+// MILEAGE_RATE in .env, in euro per kilometer
+fn rate() -> Result<f64, Box<dyn Error>> {
+    match env::var("MILEAGE_RATE") {
+        Ok(value) if !value.trim().is_empty() => value
+            .trim()
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|r| *r >= 0.0)
+            .ok_or_else(|| format!("MILEAGE_RATE: invalid amount '{}'", value).into()),
+        _ => Ok(DEFAULT_RATE),
+    }
+}
+
+fn euro(amount: f64) -> String {
+    format!("€ {:.2}", amount)
+}
+
+// Trips of a month, optionally of one project, by date
+pub fn month_trips(conn: &Connection, year: i32, month: u32, project: Option<&str>) -> Result<Vec<Trip>, Box<dyn Error>> {
+    let (first, last) = month_bounds(year, month)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, date, project, origin, destination, km, mode, rate FROM trips
+         WHERE date BETWEEN ?1 AND ?2 AND (?3 IS NULL OR project = ?3)
+         ORDER BY date, id",
+    )?;
+    let rows = stmt.query_map(params![first.to_string(), last.to_string(), project], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get::<_, String>(6)?, row.get(7)?))
+    })?;
+
+    let mut trips = Vec::new();
+    for row in rows {
+        let (id, date, project, origin, destination, km, mode, rate) = row?;
+        let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")?;
+        let mode = TransportMode::parse(&mode);
+        let label = format!("{} {}: {} - {} ({} km)", date.format("%d-%m-%Y"), project, origin, destination, km);
+        trips.push(Trip { id, date, project, origin, destination, km, mode, rate, label });
+    }
+    Ok(trips)
+}
+
+fn print_trips(tr: &Catalog, trips: &[Trip]) {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.date_column),
+        Cell::new(tr.project),
+        Cell::new(tr.from_column),
+        Cell::new(tr.to_column),
+        Cell::new(tr.mode_column),
+        Cell::new(tr.km_column),
+        Cell::new(tr.rate_column),
+        Cell::new(tr.allowance_column),
+    ]));
+    for trip in trips {
+        table.add_row(Row::new(vec![
+            Cell::new(&trip.date.format("%d-%m-%Y").to_string()),
+            Cell::new(&trip.project),
+            Cell::new(&trip.origin),
+            Cell::new(&trip.destination),
+            Cell::new(trip.mode.label(tr)),
+            Cell::new(&trip.km.to_string()),
+            Cell::new(&euro(trip.rate)),
+            Cell::new(&euro(trip.allowance())),
+        ]));
+    }
+    let km: f64 = trips.iter().map(|t| t.km).sum();
+    let allowance: f64 = trips.iter().map(Trip::allowance).sum();
+    table.add_row(Row::new(vec![
+        Cell::new(tr.total), Cell::new(""), Cell::new(""), Cell::new(""), Cell::new(""),
+        Cell::new(&km.to_string()), Cell::new(""), Cell::new(&euro(allowance)),
+    ]));
+    table.printstd();
+}
+
+// Asks for a new trip; None when a prompt is cancelled or the input is refused
+fn prompt_trip(conn: &Connection, repo: &dyn Repository, tr: &Catalog, default_date: NaiveDate) -> Result<Option<Trip>, Box<dyn Error>> {
+    let Ok(date) = CustomType::<NaiveDate>::new(tr.trip_date).with_default(default_date).prompt() else { return Ok(None); };
+
+    let Some(project) = choose_project(repo, tr)? else { return Ok(None); };
+    if lock::refuse_month(conn, tr, &project, date)? {
+        return Ok(None);
+    }
+
+    let Ok(origin) = Text::new(tr.trip_from).prompt() else { return Ok(None); };
+    let Ok(destination) = Text::new(tr.trip_to).prompt() else { return Ok(None); };
+    let Ok(km) = CustomType::<f64>::new(tr.trip_km).prompt() else { return Ok(None); };
+    if km <= 0.0 {
+        println!("{}", tr.invalid_km);
+        return Ok(None);
+    }
+    let modes: Vec<&str> = TransportMode::ALL.iter().map(|m| m.label(tr)).collect();
+    let Ok(choice) = Select::new(tr.trip_mode, modes).raw_prompt() else { return Ok(None); };
+
+    Ok(Some(Trip {
+        id: 0,
+        date,
+        project,
+        origin: origin.trim().to_string(),
+        destination: destination.trim().to_string(),
+        km,
+        mode: TransportMode::ALL[choice.index],
+        rate: rate()?,
+        label: String::new(),
+    }))
+}
+
+// --- Function: Register trips ---
+pub fn handle_travel(conn: &Connection, repo: &dyn Repository, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+    let today = Local::now().date_naive();
+    let year = CustomType::<i32>::new(tr.year).with_default(today.year()).prompt()?;
+    let month = CustomType::<u32>::new(tr.month).with_default(today.month()).prompt()?;
+    let (first, last) = month_bounds(year, month)?;
+    // An invalid MILEAGE_RATE is reported before any trip is entered
+    rate()?;
+
+    loop {
+        println!("\n{}", Catalog::fill(tr.travel_title, &[&month, &year]));
+        let trips = month_trips(conn, year, month, None)?;
+        if trips.is_empty() {
+            println!("{}", Catalog::fill(tr.no_trips, &[&month, &year]));
+        } else {
+            print_trips(tr, &trips);
+        }
+
+        match Select::new(tr.action, vec![tr.add_trip, tr.remove_trip, tr.exit]).prompt() {
+            Ok(a) if a == tr.add_trip => {
+                let default_date = today.clamp(first, last);
+                if let Some(trip) = prompt_trip(conn, repo, tr, default_date)? {
+                    conn.execute(
+                        "INSERT INTO trips (date, project, origin, destination, km, mode, rate) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![trip.date.to_string(), trip.project, trip.origin, trip.destination, trip.km, trip.mode.code(), trip.rate],
+                    )?;
+                }
+            },
+            Ok(a) if a == tr.remove_trip => {
+                if trips.is_empty() { continue; }
+                let Ok(trip) = Select::new(tr.select_trip, trips).prompt() else { continue; };
+                if lock::refuse_month(conn, tr, &trip.project, trip.date)? { continue; }
+                if Confirm::new(tr.are_you_sure).prompt().unwrap_or(false) {
+                    conn.execute("DELETE FROM trips WHERE id = ?1", params![trip.id])?;
+                }
+            },
+            _ => break,
+        }
+    }
+    Ok(())
+}
+
+// --- Function: Mileage report ---
+// All trips of a month, then kilometers and allowance per project
+pub fn handle_mileage(conn: &Connection, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+    let now = Local::now();
+    let default_year = now.year();
+    let default_month = if now.month() == 1 { 12 } else { now.month() - 1 };
+
+    println!("\n{}", tr.mileage_title);
+    let year = CustomType::<i32>::new(tr.year).with_default(default_year).prompt()?;
+    let month = CustomType::<u32>::new(tr.month).with_default(default_month).prompt()?;
+
+    let trips = month_trips(conn, year, month, None)?;
+    if trips.is_empty() {
+        println!("{}", Catalog::fill(tr.no_trips, &[&month, &year]));
+        return Ok(());
+    }
+    print_trips(tr, &trips);
+
+    // (trips, km, allowance) per project
+    let mut projects: BTreeMap<&str, (usize, f64, f64)> = BTreeMap::new();
+    for trip in &trips {
+        let totals = projects.entry(&trip.project).or_insert((0, 0.0, 0.0));
+        totals.0 += 1;
+        totals.1 += trip.km;
+        totals.2 += trip.allowance();
+    }
+
+    println!("\n{}", Catalog::fill(tr.mileage_report, &[&month, &year]));
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(Row::new(vec![
+        Cell::new(tr.project), Cell::new(tr.trips_column), Cell::new(tr.km_column), Cell::new(tr.allowance_column),
+    ]));
+    for (project, (count, km, allowance)) in &projects {
+        table.add_row(Row::new(vec![
+            Cell::new(project), Cell::new(&count.to_string()), Cell::new(&km.to_string()), Cell::new(&euro(*allowance)),
+        ]));
+    }
+    table.printstd();
+    Ok(())
+}
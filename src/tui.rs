@@ -0,0 +1,265 @@
+use chrono::{Datelike, Days, Local};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{self, ClearType},
+};
+use rusqlite::Connection;
+use std::{
+    error::Error,
+    io::{self, Write},
+};
+use timesheet_core::{parse_week, week_date, week_key, Repository, WeekEntry};
+
+use crate::{
+    audit::{ChangeSet, ChangeSource},
+    categories, format_hours,
+    locale::Catalog,
+    lock,
+    validate::{Rejected, Violation},
+};
+
+// --- Week Grid ---
+// Full-screen variant of `log`: the week table is an editable grid. Every edit is written
+// through a ChangeSet, so locks, validation, the change log and undo work as in `log`.
+
+const PROJECT_WIDTH: usize = 16;
+const DAY_WIDTH: usize = 7;
+
+enum Mode {
+    Navigate,
+    EditHours(String),  // the value being typed into the selected cell
+    AddProject(String), // the name of a new project row, "Project / category" for a category
+}
+
+struct Grid {
+    week: String,
+    rows: Vec<WeekEntry>,
+    row: usize,
+    col: usize, // 0 = Monday
+    mode: Mode,
+    status: String,
+}
+
+// Restores the terminal, also when the grid returns with an error
+struct RawScreen;
+
+impl RawScreen {
+    fn enter() -> Result<RawScreen, Box<dyn Error>> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(RawScreen)
+    }
+}
+
+impl Drop for RawScreen {
+    fn drop(&mut self) {
+        execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen).ok();
+        terminal::disable_raw_mode().ok();
+    }
+}
+
+// The week before (-1) or after (+1) a "YYYY-W##" week
+fn shift_week(week: &str, direction: i64) -> Option<String> {
+    let monday = week_date(week, 0)?;
+    let shifted = if direction < 0 { monday.checked_sub_days(Days::new(7))? } else { monday.checked_add_days(Days::new(7))? };
+    Some(week_key(shifted))
+}
+
+impl Grid {
+    fn load(&mut self, repo: &dyn Repository) -> Result<(), Box<dyn Error>> {
+        self.rows = repo.entries(Some(&self.week), None)?;
+        self.row = self.row.min(self.rows.len().saturating_sub(1));
+        Ok(())
+    }
+
+    // Hours as shown, with the value being typed counted in the live totals
+    fn hours(&self) -> Vec<[f64; 7]> {
+        let mut hours: Vec<[f64; 7]> = self.rows.iter().map(|e| e.hours).collect();
+        if let Mode::EditHours(input) = &self.mode
+            && let Some(value) = parse_hours(input)
+            && let Some(row) = hours.get_mut(self.row)
+        {
+            row[self.col] = value;
+        }
+        hours
+    }
+
+    fn draw(&self, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+        let mut out = io::stdout();
+        queue!(out, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+        queue!(out, SetAttribute(Attribute::Bold), Print(Catalog::fill(tr.timesheet_title, &[&self.week])), SetAttribute(Attribute::Reset))?;
+
+        let mut header = format!("{:<w$}", tr.project, w = PROJECT_WIDTH);
+        for day in tr.weekdays {
+            header.push_str(&format!("{:>w$}", day, w = DAY_WIDTH));
+        }
+        header.push_str(&format!("{:>w$}", tr.total, w = DAY_WIDTH + 1));
+        queue!(out, cursor::MoveTo(0, 2), SetAttribute(Attribute::Underlined), Print(header), SetAttribute(Attribute::Reset))?;
+
+        let hours = self.hours();
+        let mut line = 3;
+        for (i, (entry, row)) in self.rows.iter().zip(&hours).enumerate() {
+            let name: String = categories::row_label(tr, &entry.project, &entry.category).chars().take(PROJECT_WIDTH - 1).collect();
+            queue!(out, cursor::MoveTo(0, line), Print(format!("{:<w$}", name, w = PROJECT_WIDTH)))?;
+            for (day, value) in row.iter().enumerate() {
+                let selected = i == self.row && day == self.col;
+                let text = match &self.mode {
+                    Mode::EditHours(input) if selected => format!("{}_", input),
+                    _ => format_hours(*value),
+                };
+                if selected { queue!(out, SetAttribute(Attribute::Reverse))?; }
+                queue!(out, Print(format!("{:>w$}", text, w = DAY_WIDTH)), SetAttribute(Attribute::Reset))?;
+            }
+            let total: f64 = row.iter().sum();
+            queue!(out, SetAttribute(Attribute::Bold), Print(format!("{:>w$}", format_hours(total), w = DAY_WIDTH + 1)), SetAttribute(Attribute::Reset))?;
+            line += 1;
+        }
+
+        // Day totals and the week total
+        let mut totals = format!("{:<w$}", tr.total, w = PROJECT_WIDTH);
+        for day in 0..7 {
+            totals.push_str(&format!("{:>w$}", format_hours(hours.iter().map(|r| r[day]).sum()), w = DAY_WIDTH));
+        }
+        let week_total: f64 = hours.iter().flatten().sum();
+        totals.push_str(&format!("{:>w$}", format_hours(week_total), w = DAY_WIDTH + 1));
+        queue!(out, cursor::MoveTo(0, line + 1), SetAttribute(Attribute::Bold), Print(totals), SetAttribute(Attribute::Reset))?;
+
+        let prompt = match &self.mode {
+            Mode::AddProject(name) => format!("{} {}_", tr.project_name, name),
+            _ => self.status.clone(),
+        };
+        queue!(out, cursor::MoveTo(0, line + 3), Print(prompt))?;
+        queue!(out, cursor::MoveTo(0, line + 5), SetAttribute(Attribute::Dim), Print(tr.grid_help), SetAttribute(Attribute::Reset))?;
+        out.flush()?;
+        Ok(())
+    }
+
+    // Writes one cell; locks and validation errors end up in the status line
+    fn save_cell(&mut self, conn: &Connection, repo: &dyn Repository, tr: &Catalog, value: f64) -> Result<(), Box<dyn Error>> {
+        let WeekEntry { project, category, .. } = self.rows[self.row].clone();
+        if let Some(date) = lock::locked_day(conn, &project, &self.week, &[self.col])? {
+            self.status = Catalog::fill(tr.day_locked, &[&project, &date, &date.month(), &date.year()]);
+            return Ok(());
+        }
+        let changes = ChangeSet::begin(conn, repo, ChangeSource::Prompt)?;
+        changes.set_day(&self.week, &project, &category, self.col, value)?;
+        self.commit(changes, tr)?;
+        self.load(repo)
+    }
+
+    fn add_project(&mut self, conn: &Connection, repo: &dyn Repository, tr: &Catalog, name: &str) -> Result<(), Box<dyn Error>> {
+        let (project, category) = match name.split_once('/') {
+            Some((project, category)) => (project.trim(), categories::parse(tr, category)),
+            None => (name, String::new()),
+        };
+        let is_row = |e: &WeekEntry| e.project == project && e.category == category;
+        if project.is_empty() || self.rows.iter().any(is_row) { return Ok(()); }
+        let changes = ChangeSet::begin(conn, repo, ChangeSource::Prompt)?;
+        changes.insert_row(&self.week, project, &category, [0.0; 7], None)?;
+        self.commit(changes, tr)?;
+        self.load(repo)?;
+        self.row = self.rows.iter().position(is_row).unwrap_or(0);
+        Ok(())
+    }
+
+    fn commit(&mut self, changes: ChangeSet, tr: &Catalog) -> Result<(), Box<dyn Error>> {
+        let messages = |violations: &[Violation]| violations.iter().map(|v| v.message(tr)).collect::<Vec<_>>().join("  ");
+        match changes.commit() {
+            Ok(committed) => self.status = messages(&committed.warnings),
+            Err(e) => match e.downcast_ref::<Rejected>() {
+                Some(Rejected(errors)) => self.status = messages(errors),
+                None => return Err(e),
+            },
+        }
+        Ok(())
+    }
+}
+
+fn parse_hours(input: &str) -> Option<f64> {
+    if input.is_empty() { return Some(0.0); }
+    input.replace(',', ".").parse().ok()
+}
+
+// --- Function: Full-screen week grid ---
+pub fn handle_grid(conn: &Connection, repo: &dyn Repository, tr: &Catalog, week: Option<String>) -> Result<(), Box<dyn Error>> {
+    let week = week.unwrap_or_else(|| week_key(Local::now().date_naive()));
+    if parse_week(&week).is_none() {
+        return Err(format!("Invalid week '{}', expected YYYY-W##", week).into());
+    }
+    let mut grid = Grid { week, rows: Vec::new(), row: 0, col: 0, mode: Mode::Navigate, status: String::new() };
+    grid.load(repo)?;
+
+    let _screen = RawScreen::enter()?;
+    loop {
+        grid.draw(tr)?;
+        let Event::Key(key) = event::read()? else { continue; };
+        if key.kind != KeyEventKind::Press { continue; }
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) { break; }
+
+        match std::mem::replace(&mut grid.mode, Mode::Navigate) {
+            Mode::Navigate => {
+                grid.status.clear();
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Up => grid.row = grid.row.saturating_sub(1),
+                    KeyCode::Down => grid.row = (grid.row + 1).min(grid.rows.len().saturating_sub(1)),
+                    KeyCode::Left => grid.col = grid.col.saturating_sub(1),
+                    KeyCode::Right | KeyCode::Tab => grid.col = (grid.col + 1).min(6),
+                    KeyCode::PageUp | KeyCode::PageDown => {
+                        let direction = if key.code == KeyCode::PageUp { -1 } else { 1 };
+                        if let Some(week) = shift_week(&grid.week, direction) {
+                            grid.week = week;
+                            grid.load(repo)?;
+                        }
+                    }
+                    KeyCode::Char('a') => grid.mode = Mode::AddProject(String::new()),
+                    KeyCode::Enter if !grid.rows.is_empty() => {
+                        let current = grid.rows[grid.row].hours[grid.col];
+                        grid.mode = Mode::EditHours(format_hours(current));
+                    }
+                    KeyCode::Char(c) if !grid.rows.is_empty() && (c.is_ascii_digit() || c == '.' || c == ',' || c == '-') => {
+                        grid.mode = Mode::EditHours(c.to_string());
+                    }
+                    KeyCode::Delete | KeyCode::Backspace if !grid.rows.is_empty() => grid.save_cell(conn, repo, tr, 0.0)?,
+                    _ => {}
+                }
+            }
+            Mode::EditHours(mut input) => match key.code {
+                KeyCode::Esc => {}
+                KeyCode::Enter | KeyCode::Tab => match parse_hours(&input) {
+                    Some(value) => {
+                        grid.save_cell(conn, repo, tr, value)?;
+                        if key.code == KeyCode::Tab { grid.col = (grid.col + 1).min(6); }
+                    }
+                    None => grid.status = Catalog::fill(tr.invalid_hours, &[&input]),
+                },
+                KeyCode::Backspace => {
+                    input.pop();
+                    grid.mode = Mode::EditHours(input);
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() || c == '.' || c == ',' || c == '-' => {
+                    input.push(c);
+                    grid.mode = Mode::EditHours(input);
+                }
+                _ => grid.mode = Mode::EditHours(input),
+            },
+            Mode::AddProject(mut name) => match key.code {
+                KeyCode::Esc => {}
+                KeyCode::Enter => grid.add_project(conn, repo, tr, name.trim())?,
+                KeyCode::Backspace => {
+                    name.pop();
+                    grid.mode = Mode::AddProject(name);
+                }
+                KeyCode::Char(c) => {
+                    name.push(c);
+                    grid.mode = Mode::AddProject(name);
+                }
+                _ => grid.mode = Mode::AddProject(name),
+            },
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use chrono::Datelike;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::error::Error;
+use timesheet_core::{sqlite::DAY_COLUMNS, Repository};
+
+use crate::{
+    audit::{code_flag, ChangeSet, ChangeSource},
+    categories,
+    lock,
+    locale::Catalog,
+};
+
+// --- Undo / Redo ---
+// The undo stack holds batches of the change log. Undoing a batch applies the inverse of
+// its changes, redoing applies them again; both are logged as new batches themselves.
+
+// One project row of a batch with the day values before and after
+struct RowChange {
+    action: String, // add, edit, remove or billable
+    week: String,
+    project: String,
+    category: String,
+    billable: Option<bool>, // the row's own flag when it was added or removed
+    days: Vec<(usize, f64, f64)>, // day (0 = Monday), old, new
+}
+
+fn load_batch(conn: &Connection, batch: i64) -> Result<(String, Vec<RowChange>), Box<dyn Error>> {
+    let mut stmt = conn.prepare(
+        "SELECT changed_at, action, week, project, day, old_value, new_value, category, billable FROM changes WHERE batch = ?1 ORDER BY id",
+    )?;
+    let rows = stmt.query_map(params![batch], |row| {
+        Ok((
+            row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?, row.get::<_, f64>(5)?, row.get::<_, f64>(6)?, row.get::<_, String>(7)?,
+            row.get::<_, Option<bool>>(8)?,
+        ))
+    })?;
+
+    let mut changed_at = String::new();
+    let mut changes: Vec<RowChange> = Vec::new();
+    for r in rows {
+        let (at, action, week, project, day, old, new, category, billable) = r?;
+        changed_at = at;
+        let day = DAY_COLUMNS.iter().position(|d| *d == day).ok_or_else(|| format!("Unknown day '{}'", day))?;
+        match changes.last_mut() {
+            Some(c) if c.action == action && c.week == week && c.project == project && c.category == category => {
+                c.days.push((day, old, new))
+            }
+            _ => changes.push(RowChange { action, week, project, category, billable, days: vec![(day, old, new)] }),
+        }
+    }
+    Ok((changed_at, changes))
+}
+
+// Prints the first locked day touched by the batch, if any; a billable flag touches every
+// day of its row with hours
+fn refuse_locked(conn: &Connection, repo: &dyn Repository, tr: &Catalog, changes: &[RowChange]) -> Result<bool, Box<dyn Error>> {
+    for change in changes {
+        let days: Vec<usize> = match (change.action.as_str(), repo.entry(&change.week, &change.project, &change.category)?) {
+            ("billable", Some(entry)) => (0..7).filter(|&d| entry.hours[d] != 0.0).collect(),
+            _ => change.days.iter().map(|(d, _, _)| *d).collect(),
+        };
+        if let Some(date) = lock::locked_day(conn, &change.project, &change.week, &days)? {
+            println!("{}", Catalog::fill(tr.day_locked, &[&change.project, &date, &date.month(), &date.year()]));
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn top_of_stack(conn: &Connection, undone: bool) -> Result<Option<i64>, Box<dyn Error>> {
+    // Undo takes the newest batch, redo the batch that was undone last (the oldest undone one)
+    let sql = if undone {
+        "SELECT MIN(batch) FROM undo_stack WHERE undone = 1"
+    } else {
+        "SELECT MAX(batch) FROM undo_stack WHERE undone = 0"
+    };
+    Ok(conn.query_row(sql, [], |row| row.get::<_, Option<i64>>(0)).optional()?.flatten())
+}
+
+// Batches on top of the stack; the log menu only offers undo/redo for its own session's changes
+pub fn last_undoable(conn: &Connection) -> Result<Option<i64>, Box<dyn Error>> {
+    top_of_stack(conn, false)
+}
+
+pub fn last_redoable(conn: &Connection) -> Result<Option<i64>, Box<dyn Error>> {
+    top_of_stack(conn, true)
+}
+
+// --- Function: Undo the last change ---
+// Returns the undone batch, or None when there was nothing to undo or the period is locked
+pub fn undo(conn: &Connection, repo: &dyn Repository, tr: &Catalog) -> Result<Option<i64>, Box<dyn Error>> {
+    let Some(batch) = top_of_stack(conn, false)? else {
+        println!("{}", tr.nothing_to_undo);
+        return Ok(None);
+    };
+    let (changed_at, changes) = load_batch(conn, batch)?;
+    if refuse_locked(conn, repo, tr, &changes)? { return Ok(None); }
+
+    // Inverse operations, newest first
+    let set = ChangeSet::begin(conn, repo, ChangeSource::Undo)?;
+    for change in changes.iter().rev() {
+        match change.action.as_str() {
+            "add" => set.delete_row(&change.week, &change.project, &change.category)?,
+            "remove" => {
+                let mut hours = [0.0; 7];
+                for &(day, old, _) in &change.days { hours[day] = old; }
+                set.insert_row(&change.week, &change.project, &change.category, hours, change.billable)?;
+            }
+            "billable" => {
+                for &(_, old, _) in change.days.iter().rev() {
+                    set.set_billable(&change.week, &change.project, &change.category, code_flag(old))?;
+                }
+            }
+            _ => {
+                for &(day, old, _) in change.days.iter().rev() {
+                    set.set_day(&change.week, &change.project, &change.category, day, old)?;
+                }
+            }
+        }
+    }
+    set.mark_undone(batch, true)?;
+    set.commit()?;
+
+    println!("{}", Catalog::fill(tr.change_undone, &[&changed_at, &describe(tr, &changes)]));
+    Ok(Some(batch))
+}
+
+// --- Function: Redo the last undone change ---
+pub fn redo(conn: &Connection, repo: &dyn Repository, tr: &Catalog) -> Result<Option<i64>, Box<dyn Error>> {
+    let Some(batch) = top_of_stack(conn, true)? else {
+        println!("{}", tr.nothing_to_redo);
+        return Ok(None);
+    };
+    let (changed_at, changes) = load_batch(conn, batch)?;
+    if refuse_locked(conn, repo, tr, &changes)? { return Ok(None); }
+
+    let set = ChangeSet::begin(conn, repo, ChangeSource::Redo)?;
+    for change in &changes {
+        match change.action.as_str() {
+            "add" => {
+                let mut hours = [0.0; 7];
+                for &(day, _, new) in &change.days { hours[day] = new; }
+                set.insert_row(&change.week, &change.project, &change.category, hours, change.billable)?;
+            }
+            "remove" => set.delete_row(&change.week, &change.project, &change.category)?,
+            "billable" => {
+                for &(_, _, new) in &change.days {
+                    set.set_billable(&change.week, &change.project, &change.category, code_flag(new))?;
+                }
+            }
+            _ => {
+                for &(day, _, new) in &change.days {
+                    set.set_day(&change.week, &change.project, &change.category, day, new)?;
+                }
+            }
+        }
+    }
+    set.mark_undone(batch, false)?;
+    set.commit()?;
+
+    println!("{}", Catalog::fill(tr.change_redone, &[&changed_at, &describe(tr, &changes)]));
+    Ok(Some(batch))
+}
+
+// e.g. "Acme 2025-W03" or "Acme / Overleg 2025-W03"
+fn describe(tr: &Catalog, changes: &[RowChange]) -> String {
+    let mut rows: Vec<String> = changes.iter().map(|c| format!("{} {}", categories::row_label(tr, &c.project, &c.category), c.week)).collect();
+    rows.dedup();
+    rows.join(", ")
+}
@@ -0,0 +1,263 @@
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use std::{collections::BTreeMap, env, error::Error, fmt};
+use timesheet_core::{day_records, week_date, DayRecord, Repository};
+
+use crate::{
+    format_hours,
+    locale::{Catalog, Lang},
+};
+
+// --- Validation Rules ---
+// Limits come from .env: MAX_HOURS_PER_DAY, MAX_HOURS_PER_WEEK and HOLIDAYS (extra
+// dates, comma separated, on top of the Dutch public holidays).
+pub struct Rules {
+    pub max_per_day: f64,
+    pub max_per_week: f64,
+    extra_holidays: Vec<NaiveDate>,
+}
+
+impl Rules {
+    pub fn from_env() -> Result<Rules, Box<dyn Error>> {
+        let limit = |key: &str, default: f64| -> Result<f64, Box<dyn Error>> {
+            match env::var(key) {
+                Ok(v) if !v.trim().is_empty() => Ok(v.trim().parse().map_err(|_| format!("{} must be a number, got '{}'", key, v))?),
+                _ => Ok(default),
+            }
+        };
+
+        let mut extra_holidays = Vec::new();
+        for date in env::var("HOLIDAYS").unwrap_or_default().split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            extra_holidays.push(NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("HOLIDAYS: invalid date '{}'", date))?);
+        }
+
+        Ok(Rules {
+            max_per_day: limit("MAX_HOURS_PER_DAY", 12.0)?,
+            max_per_week: limit("MAX_HOURS_PER_WEEK", 60.0)?,
+            extra_holidays,
+        })
+    }
+
+    // Name of the holiday on this date, if any
+    pub fn holiday(&self, date: NaiveDate) -> Option<&'static str> {
+        if self.extra_holidays.contains(&date) {
+            return Some("HOLIDAYS");
+        }
+        dutch_holiday(date)
+    }
+}
+
+// Easter Sunday (anonymous Gregorian algorithm)
+fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+fn dutch_holiday(date: NaiveDate) -> Option<&'static str> {
+    let year = date.year();
+    let fixed = |m: u32, d: u32| NaiveDate::from_ymd_opt(year, m, d) == Some(date);
+
+    // Koningsdag moves to Saturday when 27 April is a Sunday
+    let kings_day = match NaiveDate::from_ymd_opt(year, 4, 27) {
+        Some(d) if d.weekday() == Weekday::Sun => d.pred_opt(),
+        d => d,
+    };
+    if fixed(1, 1) { return Some("Nieuwjaarsdag"); }
+    if kings_day == Some(date) { return Some("Koningsdag"); }
+    // Bevrijdingsdag is a national day off once every five years
+    if fixed(5, 5) && year % 5 == 0 { return Some("Bevrijdingsdag"); }
+    if fixed(12, 25) { return Some("Eerste Kerstdag"); }
+    if fixed(12, 26) { return Some("Tweede Kerstdag"); }
+
+    let easter = easter(year)?;
+    let offset = |days: u64| easter.checked_add_days(Days::new(days)) == Some(date);
+    if offset(0) { return Some("Eerste Paasdag"); }
+    if offset(1) { return Some("Tweede Paasdag"); }
+    if offset(39) { return Some("Hemelvaartsdag"); }
+    if offset(49) { return Some("Eerste Pinksterdag"); }
+    if offset(50) { return Some("Tweede Pinksterdag"); }
+    None
+}
+
+// --- Violations ---
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,   // the change is refused
+    Warning, // the change is kept, but reported
+}
+
+#[derive(Debug, Clone)]
+pub enum Kind {
+    Negative { project: String, date: NaiveDate, hours: f64 },
+    DayOverMax { date: NaiveDate, total: f64, max: f64 },
+    WeekOverMax { week: String, total: f64, max: f64 },
+    Weekend { project: String, date: NaiveDate },
+    Holiday { project: String, date: NaiveDate, name: &'static str },
+    Inactive { project: String, week: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct Violation {
+    pub severity: Severity,
+    pub kind: Kind,
+}
+
+impl Violation {
+    fn error(kind: Kind) -> Violation {
+        Violation { severity: Severity::Error, kind }
+    }
+
+    fn warning(kind: Kind) -> Violation {
+        Violation { severity: Severity::Warning, kind }
+    }
+
+    pub fn message(&self, tr: &Catalog) -> String {
+        let text = match &self.kind {
+            Kind::Negative { project, date, hours } => Catalog::fill(tr.negative_hours, &[project, date, hours]),
+            Kind::DayOverMax { date, total, max } => Catalog::fill(tr.day_over_max, &[date, total, max]),
+            Kind::WeekOverMax { week, total, max } => Catalog::fill(tr.week_over_max, &[week, total, max]),
+            Kind::Weekend { project, date } => Catalog::fill(tr.weekend_hours, &[project, date, &tr.weekday(date.weekday())]),
+            Kind::Holiday { project, date, name } => Catalog::fill(tr.holiday_hours, &[project, date, name]),
+            Kind::Inactive { project, week } => Catalog::fill(tr.inactive_project, &[project, week]),
+        };
+        let prefix = match self.severity { Severity::Error => tr.error_prefix, Severity::Warning => tr.warning_prefix };
+        format!("{}: {}", prefix, text)
+    }
+}
+
+// Returned by ChangeSet::commit when a change breaks a rule; the transaction is rolled back
+#[derive(Debug)]
+pub struct Rejected(pub Vec<Violation>);
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.0.iter().map(|v| v.message(Lang::En.catalog())).collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl Error for Rejected {}
+
+pub fn print(tr: &Catalog, violations: &[Violation]) {
+    for v in violations {
+        println!("{}", v.message(tr));
+    }
+}
+
+// Checks all hours between `from` and `to`. Week totals always use the whole ISO weeks.
+pub fn check_period(repo: &dyn Repository, rules: &Rules, from: NaiveDate, to: NaiveDate) -> Result<Vec<Violation>, Box<dyn Error>> {
+    let week_start = from - Days::new(from.weekday().num_days_from_monday() as u64);
+    let week_end = to + Days::new(6 - to.weekday().num_days_from_monday() as u64);
+    let records = day_records(repo, Some(week_start), Some(week_end), None)?;
+
+    let mut violations = Vec::new();
+    let mut per_day: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+    let mut per_week: BTreeMap<String, f64> = BTreeMap::new();
+    let mut project_weeks: Vec<(String, String)> = Vec::new();
+
+    for DayRecord { date, week, project, hours, .. } in records {
+        *per_week.entry(week.clone()).or_insert(0.0) += hours;
+        if date < from || date > to { continue; }
+        *per_day.entry(date).or_insert(0.0) += hours;
+
+        if hours < 0.0 {
+            violations.push(Violation::error(Kind::Negative { project: project.clone(), date, hours }));
+        } else if let Some(name) = rules.holiday(date) {
+            violations.push(Violation::warning(Kind::Holiday { project: project.clone(), date, name }));
+        } else if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            violations.push(Violation::warning(Kind::Weekend { project: project.clone(), date }));
+        }
+        if !project_weeks.contains(&(project.clone(), week.clone())) {
+            project_weeks.push((project, week));
+        }
+    }
+
+    for (date, total) in per_day {
+        if total > rules.max_per_day {
+            violations.push(Violation::error(Kind::DayOverMax { date, total, max: rules.max_per_day }));
+        }
+    }
+    for (week, total) in per_week {
+        if total > rules.max_per_week {
+            violations.push(Violation::error(Kind::WeekOverMax { week, total, max: rules.max_per_week }));
+        }
+    }
+    for (project, week) in project_weeks {
+        if !repo.project(&project)?.active {
+            violations.push(Violation::warning(Kind::Inactive { project, week }));
+        }
+    }
+    Ok(violations)
+}
+
+// Checks the weeks touched by a change ("YYYY-W##" keys)
+pub fn check_weeks(repo: &dyn Repository, rules: &Rules, weeks: &[String]) -> Result<Vec<Violation>, Box<dyn Error>> {
+    let mut violations = Vec::new();
+    for week in weeks {
+        let Some(monday) = week_date(week, 0) else { continue; };
+        violations.extend(check_period(repo, rules, monday, monday + Days::new(6))?);
+    }
+    Ok(violations)
+}
+
+// One changed day, as recorded by ChangeSet
+pub struct CellChange {
+    pub week: String,
+    pub project: String,
+    pub date: NaiveDate,
+    pub old: f64,
+    pub new: f64,
+}
+
+// Violations caused by a change. Rules that were already broken before are not blamed on
+// it, so existing data can still be corrected one day at a time.
+pub fn check_change(repo: &dyn Repository, rules: &Rules, changes: &[CellChange]) -> Result<Vec<Violation>, Box<dyn Error>> {
+    let mut weeks: Vec<String> = changes.iter().map(|c| c.week.clone()).collect();
+    weeks.sort();
+    weeks.dedup();
+
+    let touched = |project: &str, date: NaiveDate| changes.iter().any(|c| c.project == project && c.date == date && c.new > 0.0);
+    let violations = check_weeks(repo, rules, &weeks)?;
+    Ok(violations
+        .into_iter()
+        .filter(|v| match &v.kind {
+            Kind::Negative { project, date, .. } => changes.iter().any(|c| &c.project == project && c.date == *date && c.new < 0.0),
+            Kind::DayOverMax { date, .. } => changes.iter().any(|c| c.date == *date && c.new > c.old),
+            Kind::WeekOverMax { week, .. } => changes.iter().filter(|c| &c.week == week).map(|c| c.new - c.old).sum::<f64>() > 0.0,
+            Kind::Weekend { project, date } | Kind::Holiday { project, date, .. } => touched(project, *date),
+            Kind::Inactive { project, week } => changes.iter().any(|c| &c.project == project && &c.week == week && c.new > 0.0),
+        })
+        .collect())
+}
+
+// --- Function: Check a period and list all violations ---
+pub fn handle_check(repo: &dyn Repository, tr: &Catalog, from: NaiveDate, to: NaiveDate) -> Result<bool, Box<dyn Error>> {
+    if from > to {
+        return Err(format!("--from ({}) is after --to ({})", from, to).into());
+    }
+    let rules = Rules::from_env()?;
+    let violations = check_period(repo, &rules, from, to)?;
+
+    println!("\n{}", Catalog::fill(tr.check_title, &[&from, &to]));
+    if violations.is_empty() {
+        println!("{}", tr.no_violations);
+    }
+    print(tr, &violations);
+    println!(
+        "{}",
+        Catalog::fill(tr.check_limits, &[&format_hours(rules.max_per_day), &format_hours(rules.max_per_week)])
+    );
+    Ok(violations.iter().any(|v| v.severity == Severity::Error))
+}
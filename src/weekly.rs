@@ -0,0 +1,211 @@
+use chrono::{Datelike, Days, Local, NaiveDate, Weekday};
+use inquire::{MultiSelect, Text};
+use rust_xlsxwriter::{column_number_to_name, Formula, Workbook, Worksheet};
+use std::{collections::BTreeMap, env, error::Error};
+use timesheet_core::{day_records, parse_week, EntryFilter, Repository, WEEKDAYS};
+
+use crate::{
+    billing, employees::Employee, export_lang, layout::Layout, locale::{Catalog, Lang}, signing, write_expenses_block,
+    write_non_billable_row, write_sheet_header, write_signature_block, ExportOptions, SheetFormats, SheetHeader,
+};
+
+// Columns of the weekly grid. The days take the last seven day columns of the monthly
+// sheet, so the total stays in AH and the header and signature blocks line up.
+const PROJECT_COL: u16 = 1;
+const DESCRIPTION_COLS: (u16, u16) = (2, 25);
+const FIRST_DAY_COL: u16 = 26;
+const TOTAL_COL: u16 = 33;
+const MIN_LINES: usize = 5;
+
+struct WeekLine {
+    project: String,
+    hours: [f64; 7], // Mon..Sun, billable
+    non_billable: [f64; 7],
+}
+
+// Everything needed to render a weekly Urenstaat
+struct Weekstaat {
+    year: i32, // ISO year
+    week: u32,
+    monday: NaiveDate,
+    employee: Employee,
+    fill_date: NaiveDate,
+    lines: Vec<WeekLine>,
+}
+
+impl Weekstaat {
+    fn key(&self) -> String {
+        format!("{}-W{:02}", self.year, self.week)
+    }
+
+    // Client on the sheet: the project, or all projects when the sheet combines several
+    fn client(&self) -> String {
+        self.lines.iter().map(|l| l.project.as_str()).collect::<Vec<_>>().join(", ")
+    }
+}
+
+// --- Function: Export one ISO week (Mon-Sun) ---
+pub fn export_week(repo: &dyn Repository, employee: &Employee, tr: &Catalog, lang: Option<Lang>, opts: &ExportOptions) -> Result<(), Box<dyn Error>> {
+    let today = Local::now().date_naive().iso_week();
+    let default_week = format!("{}-W{:02}", today.year(), today.week());
+    let week_str = Text::new(tr.enter_week).with_default(&default_week).prompt()?;
+
+    let (year, week) = parse_week(week_str.trim())
+        .filter(|(y, w)| NaiveDate::from_isoywd_opt(*y, *w, Weekday::Mon).is_some())
+        .ok_or_else(|| format!("Invalid week '{}', expected YYYY-W##", week_str))?;
+    let monday = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon).ok_or("Invalid Date Calculation")?;
+    let sunday = monday + Days::new(6);
+
+    // Billable and non-billable hours per project
+    let billing = billing::load(repo, tr)?;
+    let mut hours: BTreeMap<String, [[f64; 7]; 2]> = BTreeMap::new();
+    for (i, billable) in [true, false].into_iter().enumerate() {
+        for record in day_records(&EntryFilter::billable(repo, &billing, billable), Some(monday), Some(sunday), None)? {
+            hours.entry(record.project).or_insert([[0.0; 7]; 2])[i][record.date.weekday().num_days_from_monday() as usize] += record.hours;
+        }
+    }
+
+    let week_key = format!("{}-W{:02}", year, week);
+    if hours.is_empty() {
+        println!("{}", Catalog::fill(tr.no_entries, &[&week_key]));
+        return Ok(());
+    }
+
+    let projects: Vec<String> = hours.keys().cloned().collect();
+    let selected = if projects.len() == 1 {
+        projects
+    } else {
+        MultiSelect::new(tr.select_projects_week, projects).with_all_selected_by_default().prompt()?
+    };
+    if selected.is_empty() { return Ok(()); }
+
+    // A single project keeps its own export language, a combined sheet uses the general one
+    let project = (selected.len() == 1).then(|| selected[0].as_str());
+    let layout = Layout::load(opts.layout.as_deref(), export_lang(repo, lang, project)?)?;
+
+    let weekstaat = Weekstaat {
+        year,
+        week,
+        monday,
+        employee: employee.clone(),
+        fill_date: Local::now().date_naive(),
+        lines: selected.iter().map(|p| WeekLine { project: p.clone(), hours: hours[p][0], non_billable: hours[p][1] }).collect(),
+    };
+
+    let pathname = env::var("PATH_NAME").unwrap_or("".to_string());
+    let filename = match project {
+        Some(p) => format!("{}Urenstaat_{}_W{:02}_{}{}.xlsx", pathname, year, week, p, employee.file_suffix()),
+        None => format!("{}Urenstaat_{}_W{:02}{}.xlsx", pathname, year, week, employee.file_suffix()),
+    };
+    println!("{}", Catalog::fill(tr.filename, &[&filename]));
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    write_weekstaat_sheet(worksheet, &weekstaat, &layout)?;
+    workbook.save(&filename)?;
+    println!("{}", Catalog::fill(tr.file_generated, &[&filename]));
+
+    if opts.sign {
+        let total: f64 = weekstaat.lines.iter().flat_map(|l| l.hours.iter().chain(&l.non_billable)).sum();
+        signing::sign_file(tr, employee, &filename, &weekstaat.key(), project, total)?;
+    }
+    Ok(())
+}
+
+// Same header, formats, expenses and signatures as the monthly sheet, with a row per project
+fn write_weekstaat_sheet(worksheet: &mut Worksheet, weekstaat: &Weekstaat, layout: &Layout) -> Result<(), Box<dyn Error>> {
+    let labels = &layout.labels;
+    let catalog = layout.lang.catalog();
+    let line_count = weekstaat.lines.len().max(MIN_LINES);
+    let rows = layout.rows_with_lines(line_count as u32, 0, 0);
+    let client = weekstaat.client();
+    let (desc_first, desc_last) = DESCRIPTION_COLS;
+
+    worksheet.protect();
+
+    let f = SheetFormats::new(layout)?;
+
+    write_sheet_header(worksheet, layout, &f, &SheetHeader {
+        employee_name: &weekstaat.employee.name,
+        employee_title: &weekstaat.employee.title,
+        employee_phone: &weekstaat.employee.phone,
+        client: &client,
+        period: (&labels.week, weekstaat.key()),
+        year: weekstaat.year,
+        fill_date: weekstaat.fill_date,
+    }, rows.last)?;
+
+    // --- Calendar Header ---
+    worksheet.write_string_with_format(rows.cal + 1, PROJECT_COL, &labels.project, &f.sheet_header)?;
+    worksheet.merge_range(rows.cal + 1, desc_first, rows.cal + 1, desc_last, &labels.description, &f.sheet_header)?;
+
+    for (i, weekday) in WEEKDAYS.iter().enumerate() {
+        let col = FIRST_DAY_COL + i as u16;
+        let date = weekstaat.monday + Days::new(i as u64);
+        worksheet.write_string_with_format(rows.cal, col, catalog.weekday_short(*weekday), &f.sheet_header)?;
+        worksheet.write_string_with_format(rows.cal + 1, col, date.format("%d-%m").to_string(), &f.sheet_header)?;
+    }
+    worksheet.write_string_with_format(rows.cal + 1, TOTAL_COL, &labels.total, &f.sheet_rowtotal)?;
+
+    // --- Project Lines ---
+    // Descriptions stay editable; spare lines are unlocked for manual additions
+    let first_day = column_number_to_name(FIRST_DAY_COL);
+    let last_day = column_number_to_name(FIRST_DAY_COL + 6);
+    for r in 0..line_count {
+        let row = rows.hours + r as u32;
+        match weekstaat.lines.get(r) {
+            Some(line) => {
+                worksheet.write_string_with_format(row, PROJECT_COL, &line.project, &f.sheet_description)?;
+                worksheet.merge_range(row, desc_first, row, desc_last, "", &f.sheet_description_unlocked)?;
+                for (i, &h) in line.hours.iter().enumerate() {
+                    let col = FIRST_DAY_COL + i as u16;
+                    if h > 0.0 {
+                        worksheet.write_number_with_format(row, col, h, &f.sheet_hours)?;
+                    } else {
+                        worksheet.write_blank(row, col, &f.sheet_hours)?;
+                    }
+                }
+            }
+            None => {
+                worksheet.write_blank(row, PROJECT_COL, &f.sheet_description_unlocked)?;
+                worksheet.merge_range(row, desc_first, row, desc_last, "", &f.sheet_description_unlocked)?;
+                for col in FIRST_DAY_COL..TOTAL_COL {
+                    worksheet.write_blank(row, col, &f.sheet_hours_unlocked)?;
+                }
+            }
+        }
+
+        let formula = format!("=SUM({}{}:{}{})", first_day, row + 1, last_day, row + 1);
+        worksheet.write_formula_with_format(row, TOTAL_COL, Formula::new(formula), &f.sheet_rowtotal)?;
+    }
+
+    // --- Totals ---
+    worksheet.write_string_with_format(rows.total, PROJECT_COL, &labels.total_billable, &f.sheet_total_description)?;
+    worksheet.merge_range(rows.total, desc_first, rows.total, desc_last, "", &f.sheet_total_description)?;
+    for col in FIRST_DAY_COL..TOTAL_COL {
+        let col_char = column_number_to_name(col);
+        let formula = format!("=SUM({}{}:{}{})", col_char, rows.hours + 1, col_char, rows.total);
+        worksheet.write_formula_with_format(rows.total, col, Formula::new(formula), &f.sheet_daytotal)?;
+    }
+    let formula_grand = format!("=SUM(AH{}:AH{})", rows.hours + 1, rows.total);
+    worksheet.write_formula_with_format(rows.total, TOTAL_COL, Formula::new(formula_grand), &f.sheet_rowtotal)?;
+
+    let mut non_billable = [0.0; 7];
+    for line in &weekstaat.lines {
+        for (day, h) in line.non_billable.iter().enumerate() { non_billable[day] += h; }
+    }
+    if non_billable.iter().any(|h| *h != 0.0) {
+        worksheet.merge_range(rows.total + 1, desc_first, rows.total + 1, desc_last, "", &f.sheet_description)?;
+        write_non_billable_row(worksheet, labels, &f, rows.total + 1, FIRST_DAY_COL, &non_billable)?;
+    }
+
+    if let Some(expense_start_row) = rows.expenses {
+        write_expenses_block(worksheet, labels, &f, expense_start_row, &[])?;
+    }
+
+    if let Some(sign_row) = rows.signatures {
+        write_signature_block(worksheet, layout, &f, sign_row, &client, &weekstaat.employee, weekstaat.fill_date, None)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,565 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    model::{week_date, Project, Template, WeekEntry},
+    repository::{Repository, Result},
+};
+
+// --- Plain-Text Repository ---
+// Stores everything as TOML in one directory, so it can live in a git repository:
+//
+//     templates.toml   default hours (and category) per project
+//     projects.toml    language, active and billable flag per project
+//     2026-01.toml     the hours of January 2026, one [[entry]] per ISO week, project and category
+//
+// A week that spans two months is split over both files; each file only holds the days of
+// its own month. Days without hours are left out.
+pub struct FileRepository {
+    dir: PathBuf,
+    batch: RefCell<Option<Store>>, // pending writes between `begin` and `commit`
+}
+
+#[derive(Debug, Clone, Default)]
+struct Store {
+    templates: Vec<Template>,
+    entries: Vec<WeekEntry>,
+    projects: Vec<Project>,
+}
+
+// The days of one week as written in the files
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Days {
+    #[serde(default, skip_serializing_if = "is_zero")]
+    mon: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    tue: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    wed: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    thu: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    fri: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    sat: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    sun: f64,
+}
+
+fn is_zero(hours: &f64) -> bool {
+    *hours == 0.0
+}
+
+impl Days {
+    fn from_hours(h: [f64; 7]) -> Days {
+        Days { mon: h[0], tue: h[1], wed: h[2], thu: h[3], fri: h[4], sat: h[5], sun: h[6] }
+    }
+
+    fn hours(&self) -> [f64; 7] {
+        [self.mon, self.tue, self.wed, self.thu, self.fri, self.sat, self.sun]
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TemplateRecord {
+    project: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    category: String,
+    #[serde(flatten)]
+    days: Days,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryRecord {
+    week: String,
+    project: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    billable: Option<bool>,
+    #[serde(flatten)]
+    days: Days,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProjectRecord {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lang: Option<String>,
+    #[serde(default = "active_default")]
+    active: bool,
+    #[serde(default = "billable_default")]
+    billable: bool,
+}
+
+fn active_default() -> bool {
+    true
+}
+
+fn billable_default() -> bool {
+    true
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TemplatesFile {
+    #[serde(default, rename = "template")]
+    templates: Vec<TemplateRecord>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProjectsFile {
+    #[serde(default, rename = "project")]
+    projects: Vec<ProjectRecord>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MonthFile {
+    #[serde(default, rename = "entry")]
+    entries: Vec<EntryRecord>,
+}
+
+const TEMPLATES_FILE: &str = "templates.toml";
+const PROJECTS_FILE: &str = "projects.toml";
+
+// "2026-01.toml" -> (2026, 1)
+fn month_of_file(path: &Path) -> Option<(i32, u32)> {
+    let stem = path.file_stem()?.to_str()?;
+    if path.extension()? != "toml" || stem.len() != 7 { return None; }
+    let (year, month) = stem.split_once('-')?;
+    let month: u32 = month.parse().ok()?;
+    (1..=12).contains(&month).then_some((year.parse().ok()?, month))
+}
+
+fn read_toml<T: for<'de> Deserialize<'de> + Default>(path: &Path) -> Result<T> {
+    if !path.exists() { return Ok(T::default()); }
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?)
+}
+
+// Writes only when the content changed, and removes files that became empty
+fn write_file(path: &Path, content: Option<String>) -> Result<()> {
+    let current = fs::read_to_string(path).ok();
+    match content {
+        Some(text) if current.as_deref() != Some(text.as_str()) => {
+            fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))?
+        }
+        None if current.is_some() => fs::remove_file(path).map_err(|e| format!("{}: {}", path.display(), e))?,
+        _ => {}
+    }
+    Ok(())
+}
+
+impl FileRepository {
+    pub fn new(dir: impl Into<PathBuf>) -> FileRepository {
+        FileRepository { dir: dir.into(), batch: RefCell::new(None) }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn load(&self) -> Result<Store> {
+        if let Some(store) = self.batch.borrow().as_ref() {
+            return Ok(store.clone());
+        }
+
+        let templates: TemplatesFile = read_toml(&self.dir.join(TEMPLATES_FILE))?;
+        let projects: ProjectsFile = read_toml(&self.dir.join(PROJECTS_FILE))?;
+
+        // Merge the parts of weeks that are split over two month files
+        let mut entries: BTreeMap<(String, String, String), WeekEntry> = BTreeMap::new();
+        if self.dir.exists() {
+            let mut paths: Vec<PathBuf> = fs::read_dir(&self.dir)?.map(|e| e.map(|e| e.path())).collect::<std::io::Result<_>>()?;
+            paths.sort();
+            for path in paths {
+                let Some((year, month)) = month_of_file(&path) else { continue; };
+                let file: MonthFile = read_toml(&path)?;
+                for record in file.entries {
+                    if week_date(&record.week, 0).is_none() {
+                        return Err(format!("{}: invalid week '{}', expected YYYY-W##", path.display(), record.week).into());
+                    }
+                    let key = (record.week.clone(), record.project.clone(), record.category.clone());
+                    let entry = entries.entry(key).or_insert_with(|| WeekEntry {
+                        week: record.week.clone(),
+                        project: record.project,
+                        category: record.category,
+                        hours: [0.0; 7],
+                        billable: None,
+                    });
+                    entry.billable = record.billable.or(entry.billable);
+                    for (day, h) in record.days.hours().into_iter().enumerate() {
+                        // Days of another month belong to that month's file
+                        if week_date(&record.week, day).is_some_and(|d| d.year() == year && d.month() == month) {
+                            entry.hours[day] = h;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Store {
+            templates: templates
+                .templates
+                .into_iter()
+                .map(|t| Template { project: t.project, category: t.category, hours: t.days.hours() })
+                .collect(),
+            entries: entries.into_values().collect(),
+            projects: projects
+                .projects
+                .into_iter()
+                .map(|p| Project { name: p.name, lang: p.lang, active: p.active, billable: p.billable })
+                .collect(),
+        })
+    }
+
+    fn save(&self, store: &Store) -> Result<()> {
+        fs::create_dir_all(&self.dir).map_err(|e| format!("{}: {}", self.dir.display(), e))?;
+
+        let mut templates: Vec<&Template> = store.templates.iter().collect();
+        templates.sort_by(|a, b| a.project.cmp(&b.project));
+        let file = TemplatesFile {
+            templates: templates
+                .iter()
+                .map(|t| TemplateRecord { project: t.project.clone(), category: t.category.clone(), days: Days::from_hours(t.hours) })
+                .collect(),
+        };
+        write_file(&self.dir.join(TEMPLATES_FILE), (!file.templates.is_empty()).then(|| toml::to_string(&file)).transpose()?)?;
+
+        let mut projects: Vec<&Project> = store.projects.iter().collect();
+        projects.sort_by(|a, b| a.name.cmp(&b.name));
+        let file = ProjectsFile {
+            projects: projects
+                .iter()
+                .map(|p| ProjectRecord { name: p.name.clone(), lang: p.lang.clone(), active: p.active, billable: p.billable })
+                .collect(),
+        };
+        write_file(&self.dir.join(PROJECTS_FILE), (!file.projects.is_empty()).then(|| toml::to_string(&file)).transpose()?)?;
+
+        // Each entry goes into the files of the months it has hours in, with only those days;
+        // a row without hours goes into the month of its Monday
+        let mut months: BTreeMap<(i32, u32), MonthFile> = BTreeMap::new();
+        let mut entries: Vec<&WeekEntry> = store.entries.iter().collect();
+        entries.sort_by(|a, b| a.week.cmp(&b.week).then_with(|| a.project.cmp(&b.project)).then_with(|| a.category.cmp(&b.category)));
+        for entry in entries {
+            let dates: Vec<NaiveDate> = (0..7).filter_map(|day| week_date(&entry.week, day)).collect();
+            let mut keys: Vec<(i32, u32)> = dates
+                .iter()
+                .zip(entry.hours)
+                .filter(|(_, h)| *h != 0.0)
+                .map(|(d, _)| (d.year(), d.month()))
+                .collect();
+            keys.dedup();
+            if keys.is_empty() {
+                keys.extend(dates.first().map(|d| (d.year(), d.month())));
+            }
+            for key in keys {
+                let mut hours = entry.hours;
+                for (day, date) in dates.iter().enumerate() {
+                    if (date.year(), date.month()) != key { hours[day] = 0.0; }
+                }
+                months.entry(key).or_default().entries.push(EntryRecord {
+                    week: entry.week.clone(),
+                    project: entry.project.clone(),
+                    category: entry.category.clone(),
+                    billable: entry.billable,
+                    days: Days::from_hours(hours),
+                });
+            }
+        }
+
+        for path in fs::read_dir(&self.dir)?.map(|e| e.map(|e| e.path())).collect::<std::io::Result<Vec<_>>>()? {
+            if let Some(key) = month_of_file(&path)
+                && !months.contains_key(&key) {
+                write_file(&path, None)?;
+            }
+        }
+        for ((year, month), file) in months {
+            let text = format!("# Hours of {}-{:02}, per ISO week, project and category\n\n{}", year, month, toml::to_string(&file)?);
+            write_file(&self.dir.join(format!("{}-{:02}.toml", year, month)), Some(text))?;
+        }
+        Ok(())
+    }
+
+    // Applies a change to the pending batch, or straight to the files outside a batch
+    fn update<T>(&self, change: impl FnOnce(&mut Store) -> Result<T>) -> Result<T> {
+        let mut store = self.load()?;
+        let result = change(&mut store)?;
+        let mut batch = self.batch.borrow_mut();
+        match batch.as_mut() {
+            Some(pending) => *pending = store,
+            None => self.save(&store)?,
+        }
+        Ok(result)
+    }
+}
+
+impl Repository for FileRepository {
+    fn templates(&self) -> Result<Vec<Template>> {
+        let mut templates = self.load()?.templates;
+        templates.sort_by(|a, b| a.project.cmp(&b.project));
+        Ok(templates)
+    }
+
+    fn insert_template(&self, template: &Template) -> Result<()> {
+        self.update(|store| {
+            if store.templates.iter().any(|t| t.project == template.project) {
+                return Err(format!("A template for '{}' already exists", template.project).into());
+            }
+            store.templates.push(template.clone());
+            Ok(())
+        })
+    }
+
+    fn update_template(&self, template: &Template) -> Result<bool> {
+        self.update(|store| {
+            let Some(existing) = store.templates.iter_mut().find(|t| t.project == template.project) else { return Ok(false); };
+            existing.category = template.category.clone();
+            existing.hours = template.hours;
+            Ok(true)
+        })
+    }
+
+    fn delete_template(&self, project: &str) -> Result<bool> {
+        self.update(|store| {
+            let before = store.templates.len();
+            store.templates.retain(|t| t.project != project);
+            Ok(store.templates.len() < before)
+        })
+    }
+
+    fn entries(&self, week: Option<&str>, project: Option<&str>) -> Result<Vec<WeekEntry>> {
+        let mut entries: Vec<WeekEntry> = self
+            .load()?
+            .entries
+            .into_iter()
+            .filter(|e| week.is_none_or(|w| e.week == w) && project.is_none_or(|p| e.project == p))
+            .collect();
+        entries.sort_by(|a, b| a.week.cmp(&b.week).then_with(|| a.project.cmp(&b.project)).then_with(|| a.category.cmp(&b.category)));
+        Ok(entries)
+    }
+
+    fn insert_entry(&self, entry: &WeekEntry) -> Result<()> {
+        if week_date(&entry.week, 0).is_none() {
+            return Err(format!("Invalid week '{}', expected YYYY-W##", entry.week).into());
+        }
+        self.update(|store| {
+            if store.entries.iter().any(|e| e.week == entry.week && e.project == entry.project && e.category == entry.category) {
+                return Err(format!("'{}' is already in {}", entry.project, entry.week).into());
+            }
+            store.entries.push(entry.clone());
+            Ok(())
+        })
+    }
+
+    fn set_day(&self, week: &str, project: &str, category: &str, day: usize, hours: f64) -> Result<()> {
+        if day >= 7 {
+            return Err(format!("Invalid day {}", day).into());
+        }
+        self.update(|store| {
+            let entry = store
+                .entries
+                .iter_mut()
+                .find(|e| e.week == week && e.project == project && e.category == category)
+                .ok_or_else(|| format!("No row for {} in {}", project, week))?;
+            entry.hours[day] = hours;
+            Ok(())
+        })
+    }
+
+    fn delete_entry(&self, week: &str, project: &str, category: &str) -> Result<bool> {
+        self.update(|store| {
+            let before = store.entries.len();
+            store.entries.retain(|e| !(e.week == week && e.project == project && e.category == category));
+            Ok(store.entries.len() < before)
+        })
+    }
+
+    fn set_billable(&self, week: &str, project: &str, category: &str, billable: Option<bool>) -> Result<()> {
+        self.update(|store| {
+            let entry = store
+                .entries
+                .iter_mut()
+                .find(|e| e.week == week && e.project == project && e.category == category)
+                .ok_or_else(|| format!("No row for {} in {}", project, week))?;
+            entry.billable = billable;
+            Ok(())
+        })
+    }
+
+    fn project_settings(&self) -> Result<Vec<Project>> {
+        let mut projects = self.load()?.projects;
+        projects.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(projects)
+    }
+
+    fn save_project(&self, project: &Project) -> Result<()> {
+        self.update(|store| {
+            match store.projects.iter_mut().find(|p| p.name == project.name) {
+                Some(existing) => *existing = project.clone(),
+                None => store.projects.push(project.clone()),
+            }
+            Ok(())
+        })
+    }
+
+    fn begin(&self) -> Result<()> {
+        let store = self.load()?;
+        *self.batch.borrow_mut() = Some(store);
+        Ok(())
+    }
+
+    fn commit(&self) -> Result<()> {
+        let Some(store) = self.batch.borrow_mut().take() else { return Ok(()); };
+        self.save(&store)
+    }
+
+    fn rollback(&self) {
+        self.batch.borrow_mut().take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day_records, SqliteRepository};
+    use rusqlite::Connection;
+
+    // 2026-W05 runs from Monday 26 January to Sunday 1 February
+    const SPLIT_WEEK: &str = "2026-W05";
+
+    // An empty folder of its own per test, removed again when the test ends
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> TestDir {
+            let dir = std::env::temp_dir().join(format!("timesheet_core_{}_{}", name, std::process::id()));
+            fs::remove_dir_all(&dir).ok();
+            TestDir(dir)
+        }
+
+        fn read(&self, file: &str) -> Option<String> {
+            fs::read_to_string(self.0.join(file)).ok()
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            fs::remove_dir_all(&self.0).ok();
+        }
+    }
+
+    fn entry(week: &str, project: &str, category: &str, hours: [f64; 7]) -> WeekEntry {
+        WeekEntry { week: week.to_string(), project: project.to_string(), category: category.to_string(), hours, billable: None }
+    }
+
+    #[test]
+    fn split_week_round_trip() {
+        let dir = TestDir::new("split_week");
+        let repo = FileRepository::new(&dir.0);
+        repo.insert_entry(&entry(SPLIT_WEEK, "Acme", "", [8.0, 8.0, 8.0, 8.0, 8.0, 0.0, 2.0])).unwrap();
+
+        // Each month file only holds the days of its own month
+        let january = dir.read("2026-01.toml").unwrap();
+        let february = dir.read("2026-02.toml").unwrap();
+        assert!(january.contains("mon = 8.0") && !january.contains("sun"));
+        assert!(february.contains("sun = 2.0") && !february.contains("mon"));
+
+        // A new repository merges both halves again
+        let reloaded = FileRepository::new(&dir.0);
+        assert_eq!(reloaded.entries(None, None).unwrap(), vec![entry(SPLIT_WEEK, "Acme", "", [8.0, 8.0, 8.0, 8.0, 8.0, 0.0, 2.0])]);
+
+        // Emptying the Sunday removes the February file
+        reloaded.set_day(SPLIT_WEEK, "Acme", "", 6, 0.0).unwrap();
+        assert!(dir.read("2026-02.toml").is_none());
+        reloaded.set_day(SPLIT_WEEK, "Acme", "", 6, 1.5).unwrap();
+        assert_eq!(FileRepository::new(&dir.0).entry(SPLIT_WEEK, "Acme", "").unwrap().unwrap().hours[6], 1.5);
+
+        assert!(reloaded.delete_entry(SPLIT_WEEK, "Acme", "").unwrap());
+        assert!(dir.read("2026-01.toml").is_none() && dir.read("2026-02.toml").is_none());
+        assert!(FileRepository::new(&dir.0).entries(None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn billable_flag_round_trip() {
+        let dir = TestDir::new("billable");
+        let repo = FileRepository::new(&dir.0);
+        repo.insert_entry(&entry(SPLIT_WEEK, "Acme", "", [8.0, 0.0, 0.0, 0.0, 0.0, 0.0, 4.0])).unwrap();
+
+        repo.set_billable(SPLIT_WEEK, "Acme", "", Some(false)).unwrap();
+        // Both halves of the week carry the flag
+        assert!(dir.read("2026-01.toml").unwrap().contains("billable = false"));
+        assert!(dir.read("2026-02.toml").unwrap().contains("billable = false"));
+        assert_eq!(FileRepository::new(&dir.0).entry(SPLIT_WEEK, "Acme", "").unwrap().unwrap().billable, Some(false));
+
+        repo.set_billable(SPLIT_WEEK, "Acme", "", None).unwrap();
+        assert!(!dir.read("2026-01.toml").unwrap().contains("billable"));
+        assert_eq!(FileRepository::new(&dir.0).entry(SPLIT_WEEK, "Acme", "").unwrap().unwrap().billable, None);
+
+        assert!(repo.set_billable(SPLIT_WEEK, "Globex", "", Some(true)).is_err());
+    }
+
+    #[test]
+    fn category_rows_round_trip() {
+        let dir = TestDir::new("categories");
+        let repo = FileRepository::new(&dir.0);
+        repo.insert_entry(&entry(SPLIT_WEEK, "Acme", "", [6.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0])).unwrap();
+        repo.insert_entry(&entry(SPLIT_WEEK, "Acme", "meeting", [2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0])).unwrap();
+        assert!(repo.insert_entry(&entry(SPLIT_WEEK, "Acme", "meeting", [1.0; 7])).is_err());
+
+        repo.set_day(SPLIT_WEEK, "Acme", "meeting", 1, 3.0).unwrap();
+        let reloaded = FileRepository::new(&dir.0);
+        assert_eq!(
+            reloaded.entries(Some(SPLIT_WEEK), Some("Acme")).unwrap(),
+            vec![
+                entry(SPLIT_WEEK, "Acme", "", [6.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]),
+                entry(SPLIT_WEEK, "Acme", "meeting", [2.0, 3.0, 0.0, 0.0, 0.0, 0.0, 1.0]),
+            ]
+        );
+
+        assert!(reloaded.delete_entry(SPLIT_WEEK, "Acme", "").unwrap());
+        let left = FileRepository::new(&dir.0).entries(None, None).unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].category, "meeting");
+    }
+
+    #[test]
+    fn day_records_match_sqlite() {
+        let dir = TestDir::new("day_records");
+        let files = FileRepository::new(&dir.0);
+        let conn = Connection::open_in_memory().unwrap();
+        let sqlite = SqliteRepository::new(&conn);
+        sqlite.init().unwrap();
+
+        let entries = [
+            entry(SPLIT_WEEK, "Acme", "", [8.0, 8.0, 0.0, 0.0, 4.0, 0.0, 2.0]),
+            entry(SPLIT_WEEK, "Acme", "meeting", [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]),
+            entry(SPLIT_WEEK, "Globex", "", [0.0, 0.0, 7.5, 0.0, 0.0, 0.0, 0.0]),
+            entry("2026-W06", "Acme", "", [8.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]),
+        ];
+        for e in &entries {
+            files.insert_entry(e).unwrap();
+            sqlite.insert_entry(e).unwrap();
+        }
+        files.set_day(SPLIT_WEEK, "Globex", "", 3, 1.0).unwrap();
+        sqlite.set_day(SPLIT_WEEK, "Globex", "", 3, 1.0).unwrap();
+
+        let records = |repo: &dyn Repository, from: Option<NaiveDate>, to: Option<NaiveDate>, project: Option<&str>| {
+            day_records(repo, from, to, project)
+                .unwrap()
+                .into_iter()
+                .map(|r| (r.date, r.week, r.project, r.category, r.hours))
+                .collect::<Vec<_>>()
+        };
+        let february = (NaiveDate::from_ymd_opt(2026, 2, 1), NaiveDate::from_ymd_opt(2026, 2, 28));
+        for (from, to, project) in [(None, None, None), (february.0, february.1, None), (None, None, Some("Acme"))] {
+            assert_eq!(records(&files, from, to, project), records(&sqlite, from, to, project));
+        }
+        assert_eq!(records(&files, None, None, None).len(), 8);
+    }
+}
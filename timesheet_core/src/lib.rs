@@ -0,0 +1,18 @@
+// Core of the timesheet tool: domain types, the storage abstraction and the aggregations
+// behind the month views and exports. The `timesheet_cli` binary is one consumer.
+//
+//     let conn = rusqlite::Connection::open("timesheet.db")?;
+//     let repo = SqliteRepository::new(&conn);
+//     let hours = month_hours(&repo, 2026, 1, None)?;
+//
+// `FileRepository` stores the same data as plain TOML files, one per month.
+
+pub mod files;
+pub mod model;
+pub mod repository;
+pub mod sqlite;
+
+pub use model::{month_bounds, parse_week, week_date, week_key, DayRecord, Project, Template, WeekEntry, WEEKDAYS};
+pub use repository::{daily_totals, day_records, month_categories, month_hours, month_projects, month_totals, Billing, EntryFilter, Repository, Result};
+pub use files::FileRepository;
+pub use sqlite::SqliteRepository;
@@ -0,0 +1,90 @@
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+use serde::Serialize;
+use std::error::Error;
+
+// --- Domain Types ---
+// Hours are always an array of 7 days, Monday first.
+
+// Default hours of a project for a new week, with the category its rows get
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Template {
+    pub project: String,
+    pub category: String,
+    pub hours: [f64; 7],
+}
+
+// The hours of one project and activity category in one ISO week ("YYYY-W##"). The
+// category is empty for uncategorised hours; a project can have a row per category.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeekEntry {
+    pub week: String,
+    pub project: String,
+    pub category: String,
+    pub hours: [f64; 7],
+    pub billable: Option<bool>, // None: follows the project and category, see Billing
+}
+
+impl WeekEntry {
+    pub fn total(&self) -> f64 {
+        self.hours.iter().sum()
+    }
+}
+
+// Per-project settings; projects without settings are active, billable and use the default language
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Project {
+    pub name: String,
+    pub lang: Option<String>, // language code, e.g. "nl"
+    pub active: bool,
+    pub billable: bool,
+}
+
+impl Project {
+    pub fn new(name: &str) -> Project {
+        Project { name: name.to_string(), lang: None, active: true, billable: true }
+    }
+}
+
+// One record per date/project/category. Field names are the stable column names of `dump`;
+// new columns are added at the end.
+#[derive(Debug, Clone, Serialize)]
+pub struct DayRecord {
+    pub date: NaiveDate,
+    pub week: String,
+    pub project: String,
+    pub hours: f64,
+    pub category: String,
+}
+
+// --- Weeks and Months ---
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun,
+];
+
+// Parses the "YYYY-W##" week key
+pub fn parse_week(week_str: &str) -> Option<(i32, u32)> {
+    let (year, week) = week_str.split_once("-W")?;
+    Some((year.parse().ok()?, week.parse().ok()?))
+}
+
+// The "YYYY-W##" key of the ISO week a date falls in
+pub fn week_key(date: NaiveDate) -> String {
+    let week = date.iso_week();
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+// Date of a day (0 = Monday) in a "YYYY-W##" week
+pub fn week_date(week: &str, day: usize) -> Option<NaiveDate> {
+    let (year, week_num) = parse_week(week)?;
+    NaiveDate::from_isoywd_opt(year, week_num, *WEEKDAYS.get(day)?)
+}
+
+// First and last day of a month
+pub fn month_bounds(year: i32, month: u32) -> Result<(NaiveDate, NaiveDate), Box<dyn Error>> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).ok_or("Invalid Date Calculation")?;
+    let last = first
+        .checked_add_months(Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .ok_or("Invalid Date Calculation")?;
+    Ok((first, last))
+}
@@ -0,0 +1,241 @@
+use chrono::{Datelike, NaiveDate};
+use std::{collections::BTreeMap, error::Error};
+
+use crate::model::{month_bounds, week_date, DayRecord, Project, Template, WeekEntry};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// --- Repository ---
+// Storage of templates, weekly entries and project settings. Aggregations are built on top
+// of these operations, so every backend gets them for free.
+pub trait Repository {
+    // Templates, sorted by project
+    fn templates(&self) -> Result<Vec<Template>>;
+    // Fails when the project already has a template
+    fn insert_template(&self, template: &Template) -> Result<()>;
+    // Returns false when the project has no template
+    fn update_template(&self, template: &Template) -> Result<bool>;
+    fn delete_template(&self, project: &str) -> Result<bool>;
+
+    // Weekly entries of all categories, optionally filtered, sorted by week, project and category
+    fn entries(&self, week: Option<&str>, project: Option<&str>) -> Result<Vec<WeekEntry>>;
+    // Fails when the project already has a row with that category in that week
+    fn insert_entry(&self, entry: &WeekEntry) -> Result<()>;
+    // Sets the hours of one day (0 = Monday); fails when there is no entry
+    fn set_day(&self, week: &str, project: &str, category: &str, day: usize, hours: f64) -> Result<()>;
+    fn delete_entry(&self, week: &str, project: &str, category: &str) -> Result<bool>;
+    // Marks one row as billable or not, or (None) lets it follow its project and category
+    fn set_billable(&self, week: &str, project: &str, category: &str, billable: Option<bool>) -> Result<()>;
+
+    // Settings of projects that have them
+    fn project_settings(&self) -> Result<Vec<Project>>;
+    fn save_project(&self, project: &Project) -> Result<()>;
+
+    // Groups writes, so a rejected change leaves nothing behind. SQLite writes on the
+    // caller's connection and relies on its transaction, so these do nothing by default.
+    fn begin(&self) -> Result<()> { Ok(()) }
+    fn commit(&self) -> Result<()> { Ok(()) }
+    fn rollback(&self) {}
+
+    fn entry(&self, week: &str, project: &str, category: &str) -> Result<Option<WeekEntry>> {
+        Ok(self.entries(Some(week), Some(project))?.into_iter().find(|e| e.category == category))
+    }
+
+    fn project(&self, name: &str) -> Result<Project> {
+        let settings = self.project_settings()?;
+        Ok(settings.into_iter().find(|p| p.name == name).unwrap_or_else(|| Project::new(name)))
+    }
+
+    // Every project with a template, hours or settings, sorted by name
+    fn projects(&self) -> Result<Vec<Project>> {
+        let mut projects: BTreeMap<String, Project> = BTreeMap::new();
+        for template in self.templates()? {
+            projects.insert(template.project.clone(), Project::new(&template.project));
+        }
+        for entry in self.entries(None, None)? {
+            projects.entry(entry.project.clone()).or_insert_with(|| Project::new(&entry.project));
+        }
+        for settings in self.project_settings()? {
+            projects.insert(settings.name.clone(), settings);
+        }
+        Ok(projects.into_values().collect())
+    }
+}
+
+// --- Aggregations ---
+
+// Expands the weekly entries into dated records, skipping days without hours
+pub fn day_records(
+    repo: &dyn Repository,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    project: Option<&str>,
+) -> Result<Vec<DayRecord>> {
+    let mut records = Vec::new();
+    for entry in repo.entries(None, project)? {
+        for (day, &hours) in entry.hours.iter().enumerate() {
+            if hours == 0.0 { continue; }
+            let Some(date) = week_date(&entry.week, day) else { continue; };
+            if from.is_some_and(|f| date < f) || to.is_some_and(|t| date > t) { continue; }
+
+            records.push(DayRecord {
+                date,
+                week: entry.week.clone(),
+                project: entry.project.clone(),
+                hours,
+                category: entry.category.clone(),
+            });
+        }
+    }
+
+    records.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.project.cmp(&b.project)).then_with(|| a.category.cmp(&b.category)));
+    Ok(records)
+}
+
+// Hours per project per day of the month
+pub fn month_hours(repo: &dyn Repository, year: i32, month: u32, project: Option<&str>) -> Result<BTreeMap<String, BTreeMap<u32, f64>>> {
+    let (first, last) = month_bounds(year, month)?;
+    let mut hours: BTreeMap<String, BTreeMap<u32, f64>> = BTreeMap::new();
+    for record in day_records(repo, Some(first), Some(last), project)? {
+        *hours.entry(record.project).or_default().entry(record.date.day()).or_insert(0.0) += record.hours;
+    }
+    Ok(hours)
+}
+
+// Hours per category per day of the month; uncategorised hours are under ""
+pub fn month_categories(repo: &dyn Repository, year: i32, month: u32, project: Option<&str>) -> Result<BTreeMap<String, BTreeMap<u32, f64>>> {
+    let (first, last) = month_bounds(year, month)?;
+    let mut hours: BTreeMap<String, BTreeMap<u32, f64>> = BTreeMap::new();
+    for record in day_records(repo, Some(first), Some(last), project)? {
+        *hours.entry(record.category).or_default().entry(record.date.day()).or_insert(0.0) += record.hours;
+    }
+    Ok(hours)
+}
+
+// Total hours per project in the month
+pub fn month_totals(repo: &dyn Repository, year: i32, month: u32) -> Result<BTreeMap<String, f64>> {
+    Ok(month_hours(repo, year, month, None)?.into_iter().map(|(project, days)| (project, days.values().sum())).collect())
+}
+
+// Projects with hours in the month, sorted by name
+pub fn month_projects(repo: &dyn Repository, year: i32, month: u32) -> Result<Vec<String>> {
+    Ok(month_hours(repo, year, month, None)?.into_keys().collect())
+}
+
+// Total hours per date, over all projects
+pub fn daily_totals(repo: &dyn Repository, from: NaiveDate, to: NaiveDate) -> Result<BTreeMap<NaiveDate, f64>> {
+    let mut totals: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+    for record in day_records(repo, Some(from), Some(to), None)? {
+        *totals.entry(record.date).or_insert(0.0) += record.hours;
+    }
+    Ok(totals)
+}
+
+// --- Billable Hours ---
+// Hours are billable unless their project or category is non-billable. A row's own flag
+// overrides both, so e.g. one internal meeting can still be billed.
+pub struct Billing {
+    projects: Vec<String>,   // non-billable projects
+    categories: Vec<String>, // non-billable categories
+}
+
+impl Billing {
+    pub fn new(repo: &dyn Repository, non_billable_categories: &[String]) -> Result<Billing> {
+        let projects = repo.project_settings()?.into_iter().filter(|p| !p.billable).map(|p| p.name).collect();
+        Ok(Billing { projects, categories: non_billable_categories.to_vec() })
+    }
+
+    // Whether the hours of a project and category are billable, without a row's own flag
+    pub fn default_for(&self, project: &str, category: &str) -> bool {
+        !self.projects.iter().any(|p| p == project) && !self.categories.iter().any(|c| c == category)
+    }
+
+    pub fn is_billable(&self, entry: &WeekEntry) -> bool {
+        entry.billable.unwrap_or_else(|| self.default_for(&entry.project, &entry.category))
+    }
+}
+
+// --- Entry Filter ---
+// A view of a repository with only some of its entries, so every aggregation and report can
+// be limited to e.g. meetings or billable hours without knowing about either.
+pub struct EntryFilter<'r> {
+    repo: &'r dyn Repository,
+    keep: Keep<'r>,
+}
+
+// Whether to keep a row, from its project, category and billable flag (None for templates)
+type Keep<'r> = Box<dyn Fn(&str, &str, Option<bool>) -> bool + 'r>;
+
+impl<'r> EntryFilter<'r> {
+    // Only the entries and templates of one category
+    pub fn category(repo: &'r dyn Repository, category: &str) -> EntryFilter<'r> {
+        let category = category.to_string();
+        EntryFilter { repo, keep: Box::new(move |_, c, _| c == category) }
+    }
+
+    // Only the billable (true) or the non-billable (false) entries and templates
+    pub fn billable(repo: &'r dyn Repository, billing: &'r Billing, billable: bool) -> EntryFilter<'r> {
+        let keep = move |project: &str, category: &str, flag: Option<bool>| {
+            flag.unwrap_or_else(|| billing.default_for(project, category)) == billable
+        };
+        EntryFilter { repo, keep: Box::new(keep) }
+    }
+}
+
+impl Repository for EntryFilter<'_> {
+    fn templates(&self) -> Result<Vec<Template>> {
+        Ok(self.repo.templates()?.into_iter().filter(|t| (self.keep)(&t.project, &t.category, None)).collect())
+    }
+
+    fn insert_template(&self, template: &Template) -> Result<()> {
+        self.repo.insert_template(template)
+    }
+
+    fn update_template(&self, template: &Template) -> Result<bool> {
+        self.repo.update_template(template)
+    }
+
+    fn delete_template(&self, project: &str) -> Result<bool> {
+        self.repo.delete_template(project)
+    }
+
+    fn entries(&self, week: Option<&str>, project: Option<&str>) -> Result<Vec<WeekEntry>> {
+        Ok(self.repo.entries(week, project)?.into_iter().filter(|e| (self.keep)(&e.project, &e.category, e.billable)).collect())
+    }
+
+    fn insert_entry(&self, entry: &WeekEntry) -> Result<()> {
+        self.repo.insert_entry(entry)
+    }
+
+    fn set_day(&self, week: &str, project: &str, category: &str, day: usize, hours: f64) -> Result<()> {
+        self.repo.set_day(week, project, category, day, hours)
+    }
+
+    fn delete_entry(&self, week: &str, project: &str, category: &str) -> Result<bool> {
+        self.repo.delete_entry(week, project, category)
+    }
+
+    fn set_billable(&self, week: &str, project: &str, category: &str, billable: Option<bool>) -> Result<()> {
+        self.repo.set_billable(week, project, category, billable)
+    }
+
+    fn project_settings(&self) -> Result<Vec<Project>> {
+        self.repo.project_settings()
+    }
+
+    fn save_project(&self, project: &Project) -> Result<()> {
+        self.repo.save_project(project)
+    }
+
+    fn begin(&self) -> Result<()> {
+        self.repo.begin()
+    }
+
+    fn commit(&self) -> Result<()> {
+        self.repo.commit()
+    }
+
+    fn rollback(&self) {
+        self.repo.rollback()
+    }
+}
@@ -0,0 +1,239 @@
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::{
+    model::{Project, Template, WeekEntry},
+    repository::{Repository, Result},
+};
+
+// Names of the day columns in the templates and timesheets tables, Monday first
+pub const DAY_COLUMNS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+// --- SQLite Repository ---
+// Borrows the connection, so callers can run repository operations inside their own
+// transaction together with their own tables.
+pub struct SqliteRepository<'c> {
+    conn: &'c Connection,
+}
+
+fn hours_at(row: &Row, first: usize) -> rusqlite::Result<[f64; 7]> {
+    let mut hours = [0.0; 7];
+    for (i, h) in hours.iter_mut().enumerate() {
+        *h = row.get(first + i)?;
+    }
+    Ok(hours)
+}
+
+impl<'c> SqliteRepository<'c> {
+    pub fn new(conn: &'c Connection) -> SqliteRepository<'c> {
+        SqliteRepository { conn }
+    }
+
+    pub fn connection(&self) -> &'c Connection {
+        self.conn
+    }
+
+    // Creates the tables of templates, entries and project settings
+    pub fn init(&self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS templates (
+                id INTEGER PRIMARY KEY,
+                project TEXT NOT NULL UNIQUE,
+                mon REAL DEFAULT 0, tue REAL DEFAULT 0, wed REAL DEFAULT 0,
+                thu REAL DEFAULT 0, fri REAL DEFAULT 0, sat REAL DEFAULT 0, sun REAL DEFAULT 0
+            )",
+            [],
+        )?;
+        if !self.has_column("templates", "category")? {
+            self.conn.execute("ALTER TABLE templates ADD COLUMN category TEXT NOT NULL DEFAULT ''", [])?;
+        }
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS timesheets (
+                id INTEGER PRIMARY KEY,
+                week TEXT NOT NULL,
+                project TEXT NOT NULL,
+                category TEXT NOT NULL DEFAULT '',
+                mon REAL DEFAULT 0, tue REAL DEFAULT 0, wed REAL DEFAULT 0,
+                thu REAL DEFAULT 0, fri REAL DEFAULT 0, sat REAL DEFAULT 0, sun REAL DEFAULT 0,
+                billable INTEGER,
+                UNIQUE(week, project, category)
+            )",
+            [],
+        )?;
+        // Rows of older databases are uncategorised. The category is part of the unique key,
+        // which SQLite cannot alter, so the table is copied.
+        if !self.has_column("timesheets", "category")? {
+            // Rolled back when a statement fails, so a failed copy leaves the old table as it was
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute_batch(
+                "CREATE TABLE timesheets_categories (
+                    id INTEGER PRIMARY KEY,
+                    week TEXT NOT NULL,
+                    project TEXT NOT NULL,
+                    category TEXT NOT NULL DEFAULT '',
+                    mon REAL DEFAULT 0, tue REAL DEFAULT 0, wed REAL DEFAULT 0,
+                    thu REAL DEFAULT 0, fri REAL DEFAULT 0, sat REAL DEFAULT 0, sun REAL DEFAULT 0,
+                    UNIQUE(week, project, category)
+                 );
+                 INSERT INTO timesheets_categories (id, week, project, mon, tue, wed, thu, fri, sat, sun)
+                    SELECT id, week, project, mon, tue, wed, thu, fri, sat, sun FROM timesheets;
+                 DROP TABLE timesheets;
+                 ALTER TABLE timesheets_categories RENAME TO timesheets;",
+            )?;
+            tx.commit()?;
+        }
+        // NULL: the row follows its project and category
+        if !self.has_column("timesheets", "billable")? {
+            self.conn.execute("ALTER TABLE timesheets ADD COLUMN billable INTEGER", [])?;
+        }
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS projects (
+                name TEXT PRIMARY KEY,
+                lang TEXT
+            )",
+            [],
+        )?;
+        // Inactive projects still accept hours, but with a warning
+        if !self.has_column("projects", "active")? {
+            self.conn.execute("ALTER TABLE projects ADD COLUMN active INTEGER NOT NULL DEFAULT 1", [])?;
+        }
+        if !self.has_column("projects", "billable")? {
+            self.conn.execute("ALTER TABLE projects ADD COLUMN billable INTEGER NOT NULL DEFAULT 1", [])?;
+        }
+        Ok(())
+    }
+
+    fn has_column(&self, table: &str, column: &str) -> Result<bool> {
+        Ok(self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2", params![table, column], |row| row.get(0),
+        )?)
+    }
+}
+
+impl Repository for SqliteRepository<'_> {
+    fn templates(&self) -> Result<Vec<Template>> {
+        let mut stmt = self.conn.prepare("SELECT project, category, mon, tue, wed, thu, fri, sat, sun FROM templates ORDER BY project")?;
+        let templates = stmt
+            .query_map([], |row| Ok(Template { project: row.get(0)?, category: row.get(1)?, hours: hours_at(row, 2)? }))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(templates)
+    }
+
+    fn insert_template(&self, template: &Template) -> Result<()> {
+        let h = template.hours;
+        self.conn.execute(
+            "INSERT INTO templates (project, category, mon, tue, wed, thu, fri, sat, sun) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![template.project, template.category, h[0], h[1], h[2], h[3], h[4], h[5], h[6]],
+        )?;
+        Ok(())
+    }
+
+    fn update_template(&self, template: &Template) -> Result<bool> {
+        let h = template.hours;
+        let updated = self.conn.execute(
+            "UPDATE templates SET category=?1, mon=?2, tue=?3, wed=?4, thu=?5, fri=?6, sat=?7, sun=?8 WHERE project=?9",
+            params![template.category, h[0], h[1], h[2], h[3], h[4], h[5], h[6], template.project],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn delete_template(&self, project: &str) -> Result<bool> {
+        Ok(self.conn.execute("DELETE FROM templates WHERE project = ?1", params![project])? > 0)
+    }
+
+    fn entries(&self, week: Option<&str>, project: Option<&str>) -> Result<Vec<WeekEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT week, project, category, mon, tue, wed, thu, fri, sat, sun, billable FROM timesheets
+             WHERE (?1 IS NULL OR week = ?1) AND (?2 IS NULL OR project = ?2)
+             ORDER BY week, project, category",
+        )?;
+        let entries = stmt
+            .query_map(params![week, project], |row| {
+                Ok(WeekEntry {
+                    week: row.get(0)?,
+                    project: row.get(1)?,
+                    category: row.get(2)?,
+                    hours: hours_at(row, 3)?,
+                    billable: row.get(10)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    fn entry(&self, week: &str, project: &str, category: &str) -> Result<Option<WeekEntry>> {
+        Ok(self.conn
+            .query_row(
+                "SELECT mon, tue, wed, thu, fri, sat, sun, billable FROM timesheets WHERE week = ?1 AND project = ?2 AND category = ?3",
+                params![week, project, category],
+                |row| Ok(WeekEntry {
+                    week: week.to_string(),
+                    project: project.to_string(),
+                    category: category.to_string(),
+                    hours: hours_at(row, 0)?,
+                    billable: row.get(7)?,
+                }),
+            )
+            .optional()?)
+    }
+
+    fn insert_entry(&self, entry: &WeekEntry) -> Result<()> {
+        let h = entry.hours;
+        self.conn.execute(
+            "INSERT INTO timesheets (week, project, category, mon, tue, wed, thu, fri, sat, sun, billable)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![entry.week, entry.project, entry.category, h[0], h[1], h[2], h[3], h[4], h[5], h[6], entry.billable],
+        )?;
+        Ok(())
+    }
+
+    fn set_day(&self, week: &str, project: &str, category: &str, day: usize, hours: f64) -> Result<()> {
+        let column = DAY_COLUMNS.get(day).ok_or_else(|| format!("Invalid day {}", day))?;
+        let sql = format!("UPDATE timesheets SET {} = ?1 WHERE week = ?2 AND project = ?3 AND category = ?4", column);
+        if self.conn.execute(&sql, params![hours, week, project, category])? == 0 {
+            return Err(format!("No row for {} in {}", project, week).into());
+        }
+        Ok(())
+    }
+
+    fn delete_entry(&self, week: &str, project: &str, category: &str) -> Result<bool> {
+        Ok(self.conn.execute(
+            "DELETE FROM timesheets WHERE week = ?1 AND project = ?2 AND category = ?3", params![week, project, category],
+        )? > 0)
+    }
+
+    fn set_billable(&self, week: &str, project: &str, category: &str, billable: Option<bool>) -> Result<()> {
+        let sql = "UPDATE timesheets SET billable = ?1 WHERE week = ?2 AND project = ?3 AND category = ?4";
+        if self.conn.execute(sql, params![billable, week, project, category])? == 0 {
+            return Err(format!("No row for {} in {}", project, week).into());
+        }
+        Ok(())
+    }
+
+    fn project_settings(&self) -> Result<Vec<Project>> {
+        let mut stmt = self.conn.prepare("SELECT name, lang, active, billable FROM projects ORDER BY name")?;
+        let projects = stmt
+            .query_map([], |row| Ok(Project { name: row.get(0)?, lang: row.get(1)?, active: row.get(2)?, billable: row.get(3)? }))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(projects)
+    }
+
+    fn project(&self, name: &str) -> Result<Project> {
+        let project = self.conn
+            .query_row(
+                "SELECT name, lang, active, billable FROM projects WHERE name = ?1",
+                params![name],
+                |row| Ok(Project { name: row.get(0)?, lang: row.get(1)?, active: row.get(2)?, billable: row.get(3)? }),
+            )
+            .optional()?;
+        Ok(project.unwrap_or_else(|| Project::new(name)))
+    }
+
+    fn save_project(&self, project: &Project) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO projects (name, lang, active, billable) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(name) DO UPDATE SET lang = excluded.lang, active = excluded.active, billable = excluded.billable",
+            params![project.name, project.lang, project.active, project.billable],
+        )?;
+        Ok(())
+    }
+}